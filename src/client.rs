@@ -0,0 +1,826 @@
+//! For configuring how requests are made to the [Github] API.
+//!
+//! The blocking [`Client`] is available with the `blocking` feature, which
+//! is enabled by default. The [`AsyncClient`] is available with the `async`
+//! feature.
+//!
+//! [Github]: https://github.com/
+//! [`Client`]: struct.Client.html
+//! [`AsyncClient`]: struct.AsyncClient.html
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, LINK, USER_AGENT,
+};
+use reqwest::{Method, StatusCode, Url};
+use serde::de::DeserializeOwned;
+
+use crate::rate_limit::{self, RateLimit, RateLimitResponse, RateLimits};
+use crate::{Auth, Error, Result};
+
+/// The API that is used when no other base URL is given.
+pub const DEFAULT_BASE_URL: &str = "https://api.github.com";
+
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+const DEFAULT_ACCEPT: &str = "application/vnd.github.v3+json";
+const DEFAULT_STATS_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_STATS_MAX_WAIT: Duration = Duration::from_secs(30);
+
+/// Makes blocking requests to the [Github] API.
+///
+/// A `Client` holds on to its HTTP connection pool, so it should be created
+/// once and reused.
+///
+/// # Example
+///
+/// ```no_run
+/// use github_stats::{Client, Repo};
+///
+/// let client = Client::builder()
+///     .base_url("https://github.example.com/api/v3")
+///     .user_agent("my-dashboard")
+///     .build();
+///
+/// match client {
+///     Ok(client) => {
+///         let repo = Repo::new_with(&client, "rust-lang", "rust");
+///     }
+///     Err(e) => eprintln!(":("),
+/// }
+/// ```
+///
+/// [Github]: https://github.com/
+#[cfg(feature = "blocking")]
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::blocking::Client,
+    config: Arc<Config>,
+}
+
+/// Makes async requests to the [Github] API.
+///
+/// Like [`Client`], but every request returns a future. An `AsyncClient`
+/// must be used from within a [tokio] runtime.
+///
+/// # Example
+///
+/// ```no_run
+/// use github_stats::{ClientBuilder, Repo};
+///
+/// # async fn run() -> github_stats::Result<()> {
+/// let client = ClientBuilder::new().build_async()?;
+/// let repo = Repo::new_async(&client, "rust-lang", "rust").await?;
+///
+/// println!("{} stars", repo.stargazers_count());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [Github]: https://github.com/
+/// [`Client`]: struct.Client.html
+/// [tokio]: https://tokio.rs/
+#[cfg(feature = "async")]
+#[derive(Debug, Clone)]
+pub struct AsyncClient {
+    http: reqwest::Client,
+    config: Arc<Config>,
+}
+
+/// Configures a [`Client`] or [`AsyncClient`].
+///
+/// [`Client`]: struct.Client.html
+/// [`AsyncClient`]: struct.AsyncClient.html
+#[derive(Debug)]
+pub struct ClientBuilder {
+    base_url: String,
+    user_agent: String,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    auth: Option<Auth>,
+    wait_for_rate_limit: bool,
+    stats_backoff: Duration,
+    stats_max_wait: Duration,
+}
+
+// What `Client` and `AsyncClient` share, apart from the HTTP client itself.
+#[derive(Debug)]
+struct Config {
+    base_url: String,
+    auth: Option<Auth>,
+    wait_for_rate_limit: bool,
+    stats_backoff: Duration,
+    stats_max_wait: Duration,
+}
+
+// The waits between requests for statistics that are still being computed,
+// doubling each time until the max wait is used up.
+struct Backoff {
+    next: Duration,
+    remaining: Duration,
+}
+
+// A decoded response body, along with what its headers said.
+pub(crate) struct Response<T> {
+    pub(crate) value: T,
+    pub(crate) rate_limit: Option<RateLimit>,
+    /// The URL of the next page, from the `Link` header.
+    pub(crate) next: Option<String>,
+}
+
+#[cfg(feature = "blocking")]
+impl Client {
+    /// Creates a `Client` with the default configuration.
+    pub fn new() -> Result<Self> {
+        ClientBuilder::new().build()
+    }
+
+    /// Starts configuring a new `Client`.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// The URL that all API paths are relative to.
+    pub fn base_url(&self) -> &str {
+        &self.config.base_url
+    }
+
+    /// Gets the current rate limits for each part of the API.
+    ///
+    /// Checking the rate limits does not count against them.
+    pub fn rate_limit(&self) -> Result<RateLimits> {
+        let response: Response<RateLimitResponse> = self.get("/rate_limit")?;
+        Ok(response.value.resources)
+    }
+
+    // Makes a GET request to `path`, which is relative to the base URL.
+    pub(crate) fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Response<T>> {
+        self.get_as(path, None)
+    }
+
+    // Like `get`, but asks for the media type `accept` instead of the default
+    // one, if there is one.
+    pub(crate) fn get_as<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        accept: Option<&str>,
+    ) -> Result<Response<T>> {
+        let response = self.send(Method::GET, path, accept)?;
+        let (url, status, headers) = (
+            String::from(response.url().as_str()),
+            response.status(),
+            response.headers().clone(),
+        );
+        decode(&url, status, &headers, &response.text()?)
+    }
+
+    // Like `get_as`, but uses the default value for `204 No Content`, which
+    // Github responds with instead of an empty page for some lists, like the
+    // contributors of an empty repository.
+    pub(crate) fn get_page<T>(&self, path: &str, accept: Option<&str>) -> Result<Response<T>>
+    where
+        T: DeserializeOwned + Default,
+    {
+        let response = self.send(Method::GET, path, accept)?;
+        let (url, status, headers) = (
+            String::from(response.url().as_str()),
+            response.status(),
+            response.headers().clone(),
+        );
+        match status {
+            StatusCode::NO_CONTENT => Ok(empty(&headers)),
+            _ => decode(&url, status, &headers, &response.text()?),
+        }
+    }
+
+    // Like `get`, but for statistics that Github computes in the background.
+    // Retries while Github responds with `202 Accepted`, and uses the default
+    // value if there are no statistics, like for an empty repository.
+    pub(crate) fn get_stats<T>(&self, path: &str) -> Result<Response<T>>
+    where
+        T: DeserializeOwned + Default,
+    {
+        let mut backoff = self.config.backoff();
+        loop {
+            let response = self.send(Method::GET, path, None)?;
+            let (url, status, headers) = (
+                String::from(response.url().as_str()),
+                response.status(),
+                response.headers().clone(),
+            );
+            match status {
+                StatusCode::ACCEPTED => match backoff.next() {
+                    Some(wait) => std::thread::sleep(wait),
+                    None => return Err(Error::NotReady { url }),
+                },
+                StatusCode::NO_CONTENT => return Ok(empty(&headers)),
+                _ => return decode(&url, status, &headers, &response.text()?),
+            }
+        }
+    }
+
+    // Sends a request with this client's `Auth`, waiting out rate limits if
+    // configured to.
+    fn send(
+        &self,
+        method: Method,
+        path: &str,
+        accept: Option<&str>,
+    ) -> Result<reqwest::blocking::Response> {
+        let url = self.config.url(path);
+        loop {
+            let mut request = self.http.request(method.clone(), url.as_str());
+            if let Some(accept) = accept {
+                request = request.header(ACCEPT, accept);
+            }
+            if let Some(auth) = self.config.auth_for(&url) {
+                request = request.header(AUTHORIZATION, auth.authorization(self)?);
+            }
+            let response = request.send()?;
+
+            if self.config.wait_for_rate_limit {
+                if let Some(wait) = rate_limit::retry_wait(response.status(), response.headers()) {
+                    std::thread::sleep(wait);
+                    continue;
+                }
+            }
+            return Ok(response);
+        }
+    }
+
+    // Makes a POST request authenticated as a Github App, rather than with
+    // this client's own `Auth`.
+    pub(crate) fn post_as_app<T: DeserializeOwned>(&self, path: &str, jwt: &str) -> Result<T> {
+        let response = self
+            .http
+            .post(self.config.url(path))
+            .header(AUTHORIZATION, format!("Bearer {}", jwt))
+            .send()?;
+        let (url, status, headers) = (
+            String::from(response.url().as_str()),
+            response.status(),
+            response.headers().clone(),
+        );
+        let response: Response<T> = decode(&url, status, &headers, &response.text()?)?;
+        Ok(response.value)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncClient {
+    /// Creates an `AsyncClient` with the default configuration.
+    pub fn new() -> Result<Self> {
+        ClientBuilder::new().build_async()
+    }
+
+    /// The URL that all API paths are relative to.
+    pub fn base_url(&self) -> &str {
+        &self.config.base_url
+    }
+
+    /// Gets the current rate limits for each part of the API.
+    ///
+    /// Checking the rate limits does not count against them.
+    pub async fn rate_limit(&self) -> Result<RateLimits> {
+        let response: Response<RateLimitResponse> = self.get("/rate_limit").await?;
+        Ok(response.value.resources)
+    }
+
+    // Makes a GET request to `path`, which is relative to the base URL.
+    pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Response<T>> {
+        self.get_as(path, None).await
+    }
+
+    // Like `get`, but asks for the media type `accept` instead of the default
+    // one, if there is one.
+    pub(crate) async fn get_as<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        accept: Option<&str>,
+    ) -> Result<Response<T>> {
+        let response = self.send(Method::GET, path, accept).await?;
+        let (url, status, headers) = (
+            String::from(response.url().as_str()),
+            response.status(),
+            response.headers().clone(),
+        );
+        decode(&url, status, &headers, &response.text().await?)
+    }
+
+    // Like `get_as`, but uses the default value for `204 No Content`, which
+    // Github responds with instead of an empty page for some lists, like the
+    // contributors of an empty repository.
+    pub(crate) async fn get_page<T>(&self, path: &str, accept: Option<&str>) -> Result<Response<T>>
+    where
+        T: DeserializeOwned + Default,
+    {
+        let response = self.send(Method::GET, path, accept).await?;
+        let (url, status, headers) = (
+            String::from(response.url().as_str()),
+            response.status(),
+            response.headers().clone(),
+        );
+        match status {
+            StatusCode::NO_CONTENT => Ok(empty(&headers)),
+            _ => decode(&url, status, &headers, &response.text().await?),
+        }
+    }
+
+    // Like `get`, but for statistics that Github computes in the background.
+    // Retries while Github responds with `202 Accepted`, and uses the default
+    // value if there are no statistics, like for an empty repository.
+    pub(crate) async fn get_stats<T>(&self, path: &str) -> Result<Response<T>>
+    where
+        T: DeserializeOwned + Default,
+    {
+        let mut backoff = self.config.backoff();
+        loop {
+            let response = self.send(Method::GET, path, None).await?;
+            let (url, status, headers) = (
+                String::from(response.url().as_str()),
+                response.status(),
+                response.headers().clone(),
+            );
+            match status {
+                StatusCode::ACCEPTED => match backoff.next() {
+                    Some(wait) => tokio::time::sleep(wait).await,
+                    None => return Err(Error::NotReady { url }),
+                },
+                StatusCode::NO_CONTENT => return Ok(empty(&headers)),
+                _ => return decode(&url, status, &headers, &response.text().await?),
+            }
+        }
+    }
+
+    // Sends a request with this client's `Auth`, waiting out rate limits if
+    // configured to.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        accept: Option<&str>,
+    ) -> Result<reqwest::Response> {
+        let url = self.config.url(path);
+        loop {
+            let mut request = self.http.request(method.clone(), url.as_str());
+            if let Some(accept) = accept {
+                request = request.header(ACCEPT, accept);
+            }
+            if let Some(auth) = self.config.auth_for(&url) {
+                request = request.header(AUTHORIZATION, auth.authorization_async(self).await?);
+            }
+            let response = request.send().await?;
+
+            if self.config.wait_for_rate_limit {
+                if let Some(wait) = rate_limit::retry_wait(response.status(), response.headers()) {
+                    tokio::time::sleep(wait).await;
+                    continue;
+                }
+            }
+            return Ok(response);
+        }
+    }
+
+    // Makes a POST request authenticated as a Github App, rather than with
+    // this client's own `Auth`.
+    pub(crate) async fn post_as_app<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        jwt: &str,
+    ) -> Result<T> {
+        let response = self
+            .http
+            .post(self.config.url(path))
+            .header(AUTHORIZATION, format!("Bearer {}", jwt))
+            .send()
+            .await?;
+        let (url, status, headers) = (
+            String::from(response.url().as_str()),
+            response.status(),
+            response.headers().clone(),
+        );
+        let response: Response<T> = decode(&url, status, &headers, &response.text().await?)?;
+        Ok(response.value)
+    }
+}
+
+impl Config {
+    // `path` is relative to the base URL, unless it is already a full URL,
+    // like those in `Link` headers.
+    fn url(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            String::from(path)
+        } else {
+            format!("{}{}", self.base_url, path)
+        }
+    }
+
+    // This client's `Auth`, unless `url` is on another origin than the base
+    // URL, like a `Link` header could point to, which must not be sent the
+    // credentials.
+    fn auth_for(&self, url: &str) -> Option<&Auth> {
+        let auth = self.auth.as_ref()?;
+        match (Url::parse(&self.base_url), Url::parse(url)) {
+            (Ok(base), Ok(url)) if base.origin() == url.origin() => Some(auth),
+            _ => None,
+        }
+    }
+
+    fn backoff(&self) -> Backoff {
+        Backoff {
+            next: self.stats_backoff,
+            remaining: self.stats_max_wait,
+        }
+    }
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        if self.remaining.is_zero() {
+            return None;
+        }
+        let wait = self.next.min(self.remaining);
+        self.remaining -= wait;
+        self.next = self.next.saturating_mul(2);
+        Some(wait)
+    }
+}
+
+// Turns unsuccessful responses into errors, and decodes successful ones.
+fn decode<T: DeserializeOwned>(
+    url: &str,
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &str,
+) -> Result<Response<T>> {
+    if !status.is_success() {
+        return Err(Error::from_response(url, status, headers, body));
+    }
+
+    let value = serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(body))?;
+    Ok(Response {
+        value,
+        rate_limit: RateLimit::from_headers(headers),
+        next: next_link(headers),
+    })
+}
+
+// A response without a body.
+fn empty<T: Default>(headers: &HeaderMap) -> Response<T> {
+    Response {
+        value: T::default(),
+        rate_limit: RateLimit::from_headers(headers),
+        next: None,
+    }
+}
+
+// Finds the `rel="next"` URL in a `Link` header like
+// `<https://api.github.com/...&page=2>; rel="next", <...>; rel="last"`.
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(LINK)?.to_str().ok()?;
+    link.split(',').find_map(|link| {
+        let mut parts = link.split(';');
+        let url = parts.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+        if parts.any(|param| param.trim() == r#"rel="next""#) {
+            Some(String::from(url))
+        } else {
+            None
+        }
+    })
+}
+
+impl ClientBuilder {
+    /// Creates a new builder with the default configuration.
+    pub fn new() -> Self {
+        ClientBuilder {
+            base_url: String::from(DEFAULT_BASE_URL),
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            headers: Vec::new(),
+            timeout: None,
+            auth: None,
+            wait_for_rate_limit: false,
+            stats_backoff: DEFAULT_STATS_BACKOFF,
+            stats_max_wait: DEFAULT_STATS_MAX_WAIT,
+        }
+    }
+
+    /// Defaults to `https://api.github.com`.
+    ///
+    /// For Github Enterprise, this is usually `https://hostname/api/v3`.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = String::from(base_url.trim_end_matches('/'));
+        self
+    }
+
+    /// Defaults to `github-stats/<version>`.
+    ///
+    /// [Github] rejects requests that do not have a user agent.
+    ///
+    /// [Github]: https://github.com/
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = String::from(user_agent);
+        self
+    }
+
+    /// *Adds* a header that is sent with every request.
+    pub fn default_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((String::from(name), String::from(value)));
+        self
+    }
+
+    /// Authenticates every request. Defaults to anonymous requests.
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Sleep until the rate limit resets, then retry, instead of failing
+    /// when the rate limit is exhausted. Defaults to `false`.
+    ///
+    /// This also waits out [secondary rate limits].
+    ///
+    /// [secondary rate limits]: https://docs.github.com/en/rest/overview/resources-in-the-rest-api#secondary-rate-limits
+    pub fn wait_for_rate_limit(mut self, wait: bool) -> Self {
+        self.wait_for_rate_limit = wait;
+        self
+    }
+
+    /// How long to wait before asking for a repository's statistics again,
+    /// while [Github] is still computing them. Doubles after each wait.
+    /// Defaults to 1 second.
+    ///
+    /// [Github]: https://github.com/
+    pub fn stats_backoff(mut self, backoff: Duration) -> Self {
+        self.stats_backoff = backoff;
+        self
+    }
+
+    /// The most time to spend waiting for a repository's statistics to be
+    /// computed before failing with [`Error::NotReady`]. Defaults to 30
+    /// seconds.
+    ///
+    /// [`Error::NotReady`]: ../enum.Error.html#variant.NotReady
+    pub fn stats_max_wait(mut self, max_wait: Duration) -> Self {
+        self.stats_max_wait = max_wait;
+        self
+    }
+
+    /// Total time allowed for each request. Defaults to no timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Creates the configured `Client`.
+    ///
+    /// Fails with [`Error::InvalidHeader`] if a header is invalid.
+    ///
+    /// [`Error::InvalidHeader`]: ../enum.Error.html#variant.InvalidHeader
+    #[cfg(feature = "blocking")]
+    pub fn build(self) -> Result<Client> {
+        let http = reqwest::blocking::Client::builder()
+            .default_headers(self.headers()?)
+            .timeout(self.timeout)
+            .build()?;
+
+        Ok(Client {
+            http,
+            config: Arc::new(self.into_config()),
+        })
+    }
+
+    /// Creates the configured `AsyncClient`.
+    ///
+    /// Fails with [`Error::InvalidHeader`] if a header is invalid.
+    ///
+    /// [`Error::InvalidHeader`]: ../enum.Error.html#variant.InvalidHeader
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> Result<AsyncClient> {
+        let mut http = reqwest::Client::builder().default_headers(self.headers()?);
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+
+        Ok(AsyncClient {
+            http: http.build()?,
+            config: Arc::new(self.into_config()),
+        })
+    }
+
+    fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static(DEFAULT_ACCEPT));
+        headers.insert(USER_AGENT, HeaderValue::from_str(&self.user_agent)?);
+        for (name, value) in &self.headers {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+        Ok(headers)
+    }
+
+    fn into_config(self) -> Config {
+        Config {
+            base_url: self.base_url,
+            auth: self.auth,
+            wait_for_rate_limit: self.wait_for_rate_limit,
+            stats_backoff: self.stats_backoff,
+            stats_max_wait: self.stats_max_wait,
+        }
+    }
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        ClientBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_until_max_wait() {
+        let backoff = Backoff {
+            next: Duration::from_secs(1),
+            remaining: Duration::from_secs(10),
+        };
+        let waits: Vec<u64> = backoff.map(|wait| wait.as_secs()).collect();
+
+        assert_eq!(vec![1, 2, 4, 3], waits);
+    }
+
+    #[test]
+    fn parses_next_link() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LINK,
+            HeaderValue::from_static(
+                r#"<https://api.github.com/search/issues?q=a&page=1>; rel="prev", <https://api.github.com/search/issues?q=a&page=3>; rel="next", <https://api.github.com/search/issues?q=a&page=34>; rel="last""#,
+            ),
+        );
+
+        assert_eq!(
+            Some("https://api.github.com/search/issues?q=a&page=3"),
+            next_link(&headers).as_deref(),
+        );
+        assert_eq!(None, next_link(&HeaderMap::new()));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn invalid_header_fails_build() {
+        let client = Client::builder()
+            .default_header("bad header", "value")
+            .build();
+
+        assert!(client.is_err());
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::User;
+
+    #[test]
+    fn requests_use_base_url_and_headers() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            include_str!("../tests/fixtures/users/octocat.json"),
+        )]);
+        let client = Client::builder()
+            .base_url(&format!("{}/", server.url()))
+            .user_agent("stats-test")
+            .default_header("X-Test", "yes")
+            .build()
+            .unwrap();
+
+        let user = User::new_with(&client, "octocat").unwrap();
+        assert_eq!("octocat", user.login());
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("GET /users/octocat", requests[0].line);
+        assert_eq!(Some("stats-test"), requests[0].header("user-agent"));
+        assert_eq!(Some("yes"), requests[0].header("x-test"));
+    }
+
+    #[test]
+    fn waits_for_rate_limit_reset() {
+        let reset = (chrono::Utc::now().timestamp() + 1).to_string();
+        let server = MockServer::start(vec![
+            MockResponse::json(403, r#"{"message": "API rate limit exceeded"}"#)
+                .header("X-RateLimit-Limit", "60")
+                .header("X-RateLimit-Remaining", "0")
+                .header("X-RateLimit-Reset", &reset),
+            MockResponse::json(
+                200,
+                r#"{
+                    "resources": {
+                        "core": {"limit": 60, "remaining": 60, "reset": 1372700873, "used": 0},
+                        "search": {"limit": 10, "remaining": 9, "reset": 1372697452, "used": 1}
+                    }
+                }"#,
+            ),
+        ]);
+        let client = server.builder().wait_for_rate_limit(true).build().unwrap();
+
+        let rate_limits = client.rate_limit().unwrap();
+        assert_eq!(9, rate_limits.search().remaining());
+        assert_eq!(None, rate_limits.graphql());
+        assert_eq!(vec!["GET /rate_limit"; 2], server.request_lines());
+    }
+
+    #[test]
+    fn auth_is_only_sent_to_base_url() {
+        use crate::pagination::Paginator;
+
+        let other = MockServer::start(vec![MockResponse::json(200, "[3]")]);
+        let server = MockServer::start(vec![MockResponse::json(200, "[1, 2]").header(
+            "Link",
+            &format!(r#"<{}/leak?page=2>; rel="next""#, other.url()),
+        )]);
+        let client = server
+            .builder()
+            .auth(Auth::token("secret"))
+            .build()
+            .unwrap();
+
+        let items: Vec<u64> = Paginator::new::<Vec<u64>>(&client, "/items", None)
+            .map(|item| item.unwrap())
+            .collect();
+        assert_eq!(vec![1, 2, 3], items);
+        assert_eq!(
+            Some("token secret"),
+            server.requests()[0].header("authorization")
+        );
+        let leaked = other.requests();
+        assert_eq!("GET /leak?page=2", leaked[0].line);
+        assert_eq!(None, leaked[0].header("authorization"));
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod async_request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::User;
+
+    #[tokio::test]
+    async fn async_requests_use_base_url_and_headers() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            include_str!("../tests/fixtures/users/octocat.json"),
+        )]);
+        let client = ClientBuilder::new()
+            .base_url(server.url())
+            .user_agent("stats-test")
+            .auth(Auth::token("abc123"))
+            .build_async()
+            .unwrap();
+
+        let user = User::new_async(&client, "octocat").await.unwrap();
+        assert_eq!("octocat", user.login());
+
+        let requests = server.requests();
+        assert_eq!("GET /users/octocat", requests[0].line);
+        assert_eq!(Some("stats-test"), requests[0].header("user-agent"));
+        assert_eq!(Some("token abc123"), requests[0].header("authorization"));
+    }
+
+    #[tokio::test]
+    async fn async_waits_for_secondary_rate_limit() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                403,
+                r#"{"message": "You have exceeded a secondary rate limit"}"#,
+            )
+            .header("Retry-After", "0"),
+            MockResponse::json(
+                200,
+                r#"{
+                    "resources": {
+                        "core": {"limit": 60, "remaining": 60, "reset": 1372700873, "used": 0},
+                        "search": {"limit": 10, "remaining": 9, "reset": 1372697452, "used": 1}
+                    }
+                }"#,
+            ),
+        ]);
+        let client = server
+            .builder()
+            .wait_for_rate_limit(true)
+            .build_async()
+            .unwrap();
+
+        let rate_limits = client.rate_limit().await.unwrap();
+        assert_eq!(60, rate_limits.core().remaining());
+        assert_eq!(vec!["GET /rate_limit"; 2], server.request_lines());
+    }
+}
@@ -0,0 +1,346 @@
+//! This crate's error type.
+
+use std::error;
+use std::fmt;
+
+use chrono::prelude::{DateTime, Utc};
+use reqwest::header::{HeaderMap, InvalidHeaderName, InvalidHeaderValue};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::rate_limit;
+use crate::Period;
+
+/// Everything that can go wrong when getting stats from [Github].
+///
+/// [Github]: https://github.com/
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The request could not be sent, or its response could not be read.
+    Http(reqwest::Error),
+    /// The requested resource does not exist, or is private and the request
+    /// was not authorized to see it.
+    NotFound { url: String },
+    /// The credentials were missing, invalid, or lacked permission.
+    ///
+    /// `status` is either `401` or `403`.
+    Unauthorized { status: u16, message: String },
+    /// The rate limit is exhausted.
+    ///
+    /// Requests can be made again at `reset`.
+    RateLimited { reset: DateTime<Utc> },
+    /// The request was understood but rejected, for example because a search
+    /// query was invalid.
+    Validation {
+        message: String,
+        errors: Vec<ValidationError>,
+    },
+    /// Any other unsuccessful response.
+    Status { status: u16, message: String },
+    /// The response could not be decoded.
+    ///
+    /// `path` points to the value that failed to decode, like
+    /// `owner.login`.
+    Json {
+        path: String,
+        source: serde_json::Error,
+    },
+    /// [Github] was still computing a repository's statistics after the
+    /// client's [`stats_max_wait`].
+    ///
+    /// [Github]: https://github.com/
+    /// [`stats_max_wait`]: struct.ClientBuilder.html#method.stats_max_wait
+    NotReady { url: String },
+    /// A header given to a [`ClientBuilder`] is not a valid header.
+    ///
+    /// [`ClientBuilder`]: struct.ClientBuilder.html
+    InvalidHeader(String),
+    /// A search query would be rejected by [Github], for example because it
+    /// uses too many operators.
+    ///
+    /// [Github]: https://github.com/
+    InvalidQuery(String),
+    /// A search query could not be parsed.
+    ///
+    /// `position` is the byte offset in the query where parsing failed.
+    ParseQuery { position: usize, message: String },
+    /// A Github App key is invalid or a JWT could not be signed with it.
+    Jwt(jsonwebtoken::errors::Error),
+    /// Traffic per `found` was merged into traffic per `expected`.
+    PeriodMismatch { expected: Period, found: Period },
+}
+
+/// A single problem reported with a [`Validation`] error.
+///
+/// [`Validation`]: enum.Error.html#variant.Validation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ValidationError {
+    resource: Option<String>,
+    field: Option<String>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+    #[serde(default)]
+    errors: Vec<Value>,
+}
+
+impl Error {
+    // Creates an error from an unsuccessful response.
+    pub(crate) fn from_response(
+        url: &str,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &str,
+    ) -> Self {
+        if let Some(reset) = rate_limit::retry_at(status, headers) {
+            return Error::RateLimited { reset };
+        }
+
+        let body: ErrorBody = serde_json::from_str(body).unwrap_or_else(|_| ErrorBody {
+            message: String::from(body),
+            errors: Vec::new(),
+        });
+
+        match status {
+            StatusCode::NOT_FOUND => Error::NotFound {
+                url: String::from(url),
+            },
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Error::Unauthorized {
+                status: status.as_u16(),
+                message: body.message,
+            },
+            StatusCode::UNPROCESSABLE_ENTITY => Error::Validation {
+                message: body.message,
+                errors: body.errors.into_iter().map(ValidationError::from).collect(),
+            },
+            _ => Error::Status {
+                status: status.as_u16(),
+                message: body.message,
+            },
+        }
+    }
+}
+
+impl ValidationError {
+    /// The kind of resource that was invalid, like `"Search"`.
+    pub fn resource(&self) -> Option<&str> {
+        self.resource.as_deref()
+    }
+
+    /// The field that was invalid, like `"q"`.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// What was wrong, like `"invalid"` or `"missing_field"`.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+// Github sometimes reports errors as plain strings instead of objects.
+impl From<Value> for ValidationError {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::String(message) => ValidationError {
+                message: Some(message),
+                ..Default::default()
+            },
+            value => serde_json::from_value(value).unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Http(e) => write!(f, "HTTP error: {}", e),
+            Error::NotFound { url } => write!(f, "not found: {}", url),
+            Error::Unauthorized { status, message } => {
+                write!(f, "unauthorized ({}): {}", status, message)
+            }
+            Error::RateLimited { reset } => write!(f, "rate limit exceeded until {}", reset),
+            Error::Validation { message, errors } => {
+                write!(f, "{}", message)?;
+                for error in errors {
+                    write!(f, "; {}", error)?;
+                }
+                Ok(())
+            }
+            Error::Status { status, message } => write!(f, "status {}: {}", status, message),
+            Error::Json { path, source } => write!(f, "invalid JSON at `{}`: {}", path, source),
+            Error::NotReady { url } => write!(f, "statistics not ready: {}", url),
+            Error::InvalidHeader(e) => write!(f, "invalid header: {}", e),
+            Error::InvalidQuery(e) => write!(f, "invalid query: {}", e),
+            Error::ParseQuery { position, message } => {
+                write!(f, "{} at position {}", message, position)
+            }
+            Error::Jwt(e) => write!(f, "JWT error: {}", e),
+            Error::PeriodMismatch { expected, found } => write!(
+                f,
+                "can't merge traffic per {} into traffic per {}",
+                found.as_str(),
+                expected.as_str(),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(message) = &self.message {
+            return write!(f, "{}", message);
+        }
+        write!(
+            f,
+            "{} {} is {}",
+            self.resource.as_deref().unwrap_or("resource"),
+            self.field.as_deref().unwrap_or("field"),
+            self.code.as_deref().unwrap_or("invalid"),
+        )
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Http(e) => Some(e),
+            Error::Json { source, .. } => Some(source),
+            Error::Jwt(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for Error {
+    fn from(e: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        Error::Json {
+            path: e.path().to_string(),
+            source: e.into_inner(),
+        }
+    }
+}
+
+impl From<InvalidHeaderName> for Error {
+    fn from(e: InvalidHeaderName) -> Self {
+        Error::InvalidHeader(e.to_string())
+    }
+}
+
+impl From<InvalidHeaderValue> for Error {
+    fn from(e: InvalidHeaderValue) -> Self {
+        Error::InvalidHeader(e.to_string())
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for Error {
+    fn from(e: jsonwebtoken::errors::Error) -> Self {
+        Error::Jwt(e)
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::{Repo, User};
+
+    #[test]
+    fn not_found() {
+        let server = MockServer::start(vec![MockResponse::json(
+            404,
+            r#"{"message": "Not Found", "documentation_url": "https://docs.github.com/rest"}"#,
+        )]);
+
+        match Repo::new_with(&server.client(), "rust-lang", "missing") {
+            Err(Error::NotFound { url }) => assert!(url.ends_with("/repos/rust-lang/missing")),
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rate_limited() {
+        let server = MockServer::start(vec![MockResponse::json(
+            403,
+            r#"{"message": "API rate limit exceeded"}"#,
+        )
+        .rate_limit(60, 0)]);
+
+        match User::new_with(&server.client(), "octocat") {
+            Err(Error::RateLimited { reset }) => assert_eq!(1372700873, reset.timestamp()),
+            other => panic!("expected RateLimited, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unauthorized() {
+        let server = MockServer::start(vec![MockResponse::json(
+            401,
+            r#"{"message": "Bad credentials"}"#,
+        )]);
+
+        match User::new_with(&server.client(), "octocat") {
+            Err(Error::Unauthorized { status, message }) => {
+                assert_eq!(401, status);
+                assert_eq!("Bad credentials", message);
+            }
+            other => panic!("expected Unauthorized, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn validation_errors() {
+        let server = MockServer::start(vec![MockResponse::json(
+            422,
+            r#"{
+                "message": "Validation Failed",
+                "errors": [
+                    {"resource": "Search", "field": "q", "code": "missing"},
+                    "The listed users cannot be searched"
+                ]
+            }"#,
+        )]);
+
+        match User::new_with(&server.client(), "octocat") {
+            Err(Error::Validation { message, errors }) => {
+                assert_eq!("Validation Failed", message);
+                assert_eq!(Some("q"), errors[0].field());
+                assert_eq!(Some("missing"), errors[0].code());
+                assert_eq!(
+                    Some("The listed users cannot be searched"),
+                    errors[1].message(),
+                );
+            }
+            other => panic!("expected Validation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn json_error_path() {
+        let mut user: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/users/octocat.json")).unwrap();
+        user["id"] = "not a number".into();
+        let server = MockServer::start(vec![MockResponse::json(200, &user.to_string())]);
+
+        match User::new_with(&server.client(), "octocat") {
+            Err(Error::Json { path, .. }) => assert_eq!("id", path),
+            other => panic!("expected Json, got {:?}", other),
+        }
+    }
+}
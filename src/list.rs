@@ -0,0 +1,74 @@
+//! A list of items from one response.
+
+use crate::client::Response;
+use crate::RateLimit;
+
+/// The items of a response that is only a list, along with the rate limit
+/// that the response reported.
+///
+/// ```
+/// use github_stats::{List, PunchCardHour};
+///
+/// fn busiest(punch_card: &List<PunchCardHour>) -> Option<&PunchCardHour> {
+///     punch_card.iter().max_by_key(|hour| hour.commits())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct List<T> {
+    items: Vec<T>,
+    rate_limit: Option<RateLimit>,
+}
+
+impl<T> List<T> {
+    pub(crate) fn from_response(response: Response<Vec<T>>) -> Self {
+        List {
+            items: response.value,
+            rate_limit: response.rate_limit,
+        }
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The rate limit after getting this list.
+    ///
+    /// `None` if the response did not report it.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
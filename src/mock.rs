@@ -0,0 +1,163 @@
+// A minimal HTTP server for testing requests without touching the network.
+//
+// Each connection is answered with the next queued response, then closed.
+// Tests that use it go in a `request_tests` module, or `async_request_tests`
+// for the async client.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::ClientBuilder;
+
+pub(crate) struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+pub(crate) struct MockRequest {
+    pub(crate) line: String,
+    pub(crate) headers: Vec<(String, String)>,
+}
+
+pub(crate) struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockResponse {
+    pub(crate) fn json(status: u16, body: &str) -> Self {
+        MockResponse {
+            status,
+            headers: vec![(
+                String::from("Content-Type"),
+                String::from("application/json"),
+            )],
+            body: String::from(body),
+        }
+    }
+
+    pub(crate) fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((String::from(name), String::from(value)));
+        self
+    }
+
+    // Adds the rate limit headers, with a reset of 1372700873.
+    pub(crate) fn rate_limit(self, limit: u64, remaining: u64) -> Self {
+        self.header("X-RateLimit-Limit", &limit.to_string())
+            .header("X-RateLimit-Remaining", &remaining.to_string())
+            .header("X-RateLimit-Reset", "1372700873")
+    }
+}
+
+impl MockRequest {
+    // Header names are compared case-insensitively.
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl MockServer {
+    pub(crate) fn start(responses: Vec<MockResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+        let server_url = url.clone();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (stream, _) = match listener.accept() {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                let mut reader = BufReader::new(stream);
+                let request = read_request(&mut reader);
+                recorded.lock().unwrap().push(request);
+
+                let mut stream = reader.into_inner();
+                let mut head = format!("HTTP/1.1 {} Mock\r\n", response.status);
+                for (name, value) in &response.headers {
+                    // Lets headers like `Link` point back at this server.
+                    let value = value.replace("{url}", &server_url);
+                    head.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                head.push_str(&format!(
+                    "Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    response.body.len(),
+                ));
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(response.body.as_bytes());
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    // Configures a client that sends its requests to this server.
+    pub(crate) fn builder(&self) -> ClientBuilder {
+        ClientBuilder::new().base_url(&self.url)
+    }
+
+    #[cfg(feature = "blocking")]
+    pub(crate) fn client(&self) -> Client {
+        self.builder().build().unwrap()
+    }
+
+    // Every request since this was last called, oldest first.
+    pub(crate) fn requests(&self) -> Vec<MockRequest> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+
+    // Like `requests`, but only their methods and paths.
+    pub(crate) fn request_lines(&self) -> Vec<String> {
+        self.requests().into_iter().map(|r| r.line).collect()
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> MockRequest {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    // Drops the HTTP version so tests only compare method and path.
+    let line = line
+        .trim_end()
+        .rsplit_once(' ')
+        .map_or("", |(start, _)| start);
+
+    let mut headers = Vec::new();
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((String::from(name.trim()), String::from(value.trim())));
+        }
+    }
+
+    let length = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+
+    MockRequest {
+        line: String::from(line),
+        headers,
+    }
+}
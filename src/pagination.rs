@@ -0,0 +1,398 @@
+//! For lazily getting every item from a paginated endpoint.
+//!
+//! Pages are followed using the `rel="next"` URL from each response's `Link`
+//! header, and each page is only requested once the items from the previous
+//! page have been used up.
+
+use std::collections::VecDeque;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+#[cfg(feature = "async")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+
+#[cfg(feature = "async")]
+use futures_util::stream::{self, Stream};
+#[cfg(feature = "async")]
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+use crate::client::Response;
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{Error, RateLimit, Result};
+
+/// Iterates over the items of every page.
+///
+/// Iteration stops after the first error. [`rate_limit`] reports the rate
+/// limit after the latest page.
+///
+/// # Example
+///
+/// ```no_run
+/// use github_stats::search::Is;
+/// use github_stats::{Client, Query, Search};
+///
+/// let query = Query::new().repo("rust-lang", "rust").is(Is::Pr).is(Is::Merged);
+/// let search = Search::issues(&query).per_page(100);
+///
+/// if let Ok(client) = Client::new() {
+///     let mut items = search.items_with(&client);
+///     for item in items.by_ref().take(250) {
+///         match item {
+///             Ok(item) => { /* do stuff */ }
+///             Err(e) => eprintln!(":("),
+///         }
+///     }
+///     if let Some(rate_limit) = items.rate_limit() {
+///         println!("{} searches left", rate_limit.remaining());
+///     }
+/// }
+/// ```
+///
+/// [`rate_limit`]: #method.rate_limit
+#[cfg(feature = "blocking")]
+pub struct Paginator<T> {
+    client: Client,
+    state: State<T>,
+    fetch: Fetch<T>,
+    // Returned before anything else.
+    error: Option<Error>,
+}
+
+/// Streams the items of every page.
+///
+/// The stream ends after the first error. [`rate_limit`] reports the rate
+/// limit after the latest page.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use github_stats::search::Is;
+/// use github_stats::{ClientBuilder, Query, Search};
+///
+/// # async fn run() -> github_stats::Result<()> {
+/// let client = ClientBuilder::new().build_async()?;
+/// let query = Query::new().repo("rust-lang", "rust").is(Is::Pr).is(Is::Merged);
+/// let mut items = Search::issues(&query).items_async(&client);
+///
+/// while let Some(item) = items.next().await {
+///     let item = item?;
+///     /* do stuff */
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`rate_limit`]: #method.rate_limit
+#[cfg(feature = "async")]
+pub struct PaginatedStream<T> {
+    items: Pin<Box<dyn Stream<Item = Result<T>> + Send>>,
+    // Shared with the stream, which updates it after each page.
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+}
+
+// Requests one page, asking for a media type instead of the default one if
+// there is one.
+#[cfg(feature = "blocking")]
+type Fetch<T> = fn(&Client, &str, Option<&str>) -> Result<Response<Vec<T>>>;
+
+// Where a paginator is and what it asks for, independent of how pages are
+// requested.
+struct State<T> {
+    next: Option<String>,
+    items: VecDeque<T>,
+    limit: Option<usize>,
+    // The media type to ask for instead of the default one.
+    accept: Option<&'static str>,
+    // From the latest page.
+    rate_limit: Option<RateLimit>,
+}
+
+// A response body that holds one page of items.
+pub(crate) trait Page: DeserializeOwned {
+    type Item;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+impl<T: DeserializeOwned> Page for Vec<T> {
+    type Item = T;
+
+    fn into_items(self) -> Vec<T> {
+        self
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T> Paginator<T> {
+    // Starts at `path`, and stops after `limit` items if there is a limit.
+    pub(crate) fn new<P>(client: &Client, path: &str, limit: Option<usize>) -> Self
+    where
+        P: Page<Item = T>,
+    {
+        Paginator {
+            client: client.clone(),
+            state: State::new(path, limit),
+            fetch: fetch::<P>,
+            error: None,
+        }
+    }
+
+    // Asks for the media type `accept` for every page.
+    pub(crate) fn accept(mut self, accept: &'static str) -> Self {
+        self.state = self.state.accept(Some(accept));
+        self
+    }
+
+    // Only returns `error`.
+    pub(crate) fn failed<P>(client: &Client, error: Error) -> Self
+    where
+        P: Page<Item = T>,
+    {
+        Paginator {
+            client: client.clone(),
+            state: State {
+                next: None,
+                items: VecDeque::new(),
+                limit: None,
+                accept: None,
+                rate_limit: None,
+            },
+            fetch: fetch::<P>,
+            error: Some(error),
+        }
+    }
+
+    /// The rate limit after getting the latest page.
+    ///
+    /// `None` if no page was gotten yet, or if the response did not report
+    /// it.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.state.rate_limit.as_ref()
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T> Iterator for Paginator<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+        loop {
+            if self.state.limit_reached() {
+                return None;
+            }
+            if let Some(item) = self.state.pop() {
+                return Some(Ok(item));
+            }
+            let url = self.state.next.take()?;
+            match (self.fetch)(&self.client, &url, self.state.accept) {
+                Ok(response) => self.state.push(response),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+// Streams the items of every page, starting at `path`, and stopping after
+// `limit` items if there is a limit.
+#[cfg(feature = "async")]
+pub(crate) fn stream<P>(
+    client: &AsyncClient,
+    path: &str,
+    limit: Option<usize>,
+) -> PaginatedStream<P::Item>
+where
+    P: Page + Send,
+    P::Item: Send + 'static,
+{
+    stream_as::<P>(client, path, limit, None)
+}
+
+// Like `stream`, but asks for the media type `accept` for every page, if
+// there is one.
+#[cfg(feature = "async")]
+pub(crate) fn stream_as<P>(
+    client: &AsyncClient,
+    path: &str,
+    limit: Option<usize>,
+    accept: Option<&'static str>,
+) -> PaginatedStream<P::Item>
+where
+    P: Page + Send,
+    P::Item: Send + 'static,
+{
+    let rate_limit = Arc::new(Mutex::new(None));
+    let state = (
+        client.clone(),
+        State::new(path, limit).accept(accept),
+        Arc::clone(&rate_limit),
+    );
+    let items = stream::unfold(state, |(client, mut state, rate_limit)| async move {
+        loop {
+            if state.limit_reached() {
+                return None;
+            }
+            if let Some(item) = state.pop() {
+                return Some((Ok(item), (client, state, rate_limit)));
+            }
+            let url = state.next.take()?;
+            match client.get_page::<Option<P>>(&url, state.accept).await {
+                Ok(response) => {
+                    state.push(into_items(response));
+                    *rate_limit.lock().unwrap() = state.rate_limit.clone();
+                }
+                Err(e) => return Some((Err(e), (client, state, rate_limit))),
+            }
+        }
+    });
+    PaginatedStream {
+        items: items.boxed(),
+        rate_limit,
+    }
+}
+
+// A stream that only returns `error`.
+#[cfg(feature = "async")]
+pub(crate) fn failed_stream<T: Send + 'static>(error: Error) -> PaginatedStream<T> {
+    PaginatedStream {
+        items: stream::once(async { Err(error) }).boxed(),
+        rate_limit: Arc::default(),
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> PaginatedStream<T> {
+    /// The rate limit after getting the latest page.
+    ///
+    /// `None` if no page was gotten yet, or if the response did not report
+    /// it.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit.lock().unwrap().clone()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Stream for PaginatedStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+        self.items.as_mut().poll_next(cx)
+    }
+}
+
+impl<T> State<T> {
+    fn new(path: &str, limit: Option<usize>) -> Self {
+        State {
+            next: Some(String::from(path)),
+            items: VecDeque::new(),
+            limit,
+            accept: None,
+            rate_limit: None,
+        }
+    }
+
+    fn accept(mut self, accept: Option<&'static str>) -> Self {
+        self.accept = accept;
+        self
+    }
+
+    fn limit_reached(&self) -> bool {
+        self.limit == Some(0)
+    }
+
+    // `None` if the next page is needed.
+    fn pop(&mut self) -> Option<T> {
+        let item = self.items.pop_front()?;
+        if let Some(limit) = &mut self.limit {
+            *limit -= 1;
+        }
+        Some(item)
+    }
+
+    fn push(&mut self, response: Response<Vec<T>>) {
+        self.items.extend(response.value);
+        self.next = response.next;
+        self.rate_limit = response.rate_limit;
+    }
+}
+
+#[cfg(feature = "blocking")]
+fn fetch<P: Page>(
+    client: &Client,
+    url: &str,
+    accept: Option<&str>,
+) -> Result<Response<Vec<P::Item>>> {
+    Ok(into_items(client.get_page::<Option<P>>(url, accept)?))
+}
+
+// A page without content has no items.
+fn into_items<P: Page>(response: Response<Option<P>>) -> Response<Vec<P::Item>> {
+    Response {
+        value: response.value.map_or_else(Vec::new, Page::into_items),
+        rate_limit: response.rate_limit,
+        next: response.next,
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    #[test]
+    fn rate_limit_of_latest_page() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, "[1, 2]")
+                .header("Link", r#"<{url}/items?page=2>; rel="next""#)
+                .rate_limit(5000, 4999),
+            MockResponse::json(200, "[3]").rate_limit(5000, 4998),
+        ]);
+        let mut items = Paginator::new::<Vec<u64>>(&server.client(), "/items", None);
+
+        assert_eq!(None, items.rate_limit());
+        assert_eq!(1, items.next().unwrap().unwrap());
+        assert_eq!(4999, items.rate_limit().unwrap().remaining());
+        assert_eq!(2, items.by_ref().count());
+        assert_eq!(4998, items.rate_limit().unwrap().remaining());
+        assert_eq!(
+            vec!["GET /items", "GET /items?page=2"],
+            server.request_lines()
+        );
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod async_request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn rate_limit_of_latest_page() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, "[1, 2]")
+                .header("Link", r#"<{url}/items?page=2>; rel="next""#)
+                .rate_limit(5000, 4999),
+            MockResponse::json(200, "[3]").rate_limit(5000, 4998),
+        ]);
+        let client = server.builder().build_async().unwrap();
+        let mut items = stream::<Vec<u64>>(&client, "/items", None);
+
+        assert_eq!(None, items.rate_limit());
+        assert_eq!(1, items.next().await.unwrap().unwrap());
+        assert_eq!(4999, items.rate_limit().unwrap().remaining());
+        assert_eq!(2, items.by_ref().count().await);
+        assert_eq!(4998, items.rate_limit().unwrap().remaining());
+        assert_eq!(
+            vec!["GET /items", "GET /items?page=2"],
+            server.request_lines()
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! For checking how many requests can still be made.
+
+use std::time::Duration;
+
+use chrono::prelude::{DateTime, TimeZone, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+/// The state of a rate limit after a request.
+///
+/// Every response from the [Github] API reports this in its
+/// `X-RateLimit-*` headers.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RateLimit {
+    limit: u64,
+    remaining: u64,
+    #[serde(default)]
+    used: u64,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    reset: DateTime<Utc>,
+}
+
+/// The rate limits for each part of the API.
+///
+/// The search API has a separate, much lower limit than the rest of the API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimits {
+    core: RateLimit,
+    search: RateLimit,
+    graphql: Option<RateLimit>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RateLimitResponse {
+    pub(crate) resources: RateLimits,
+}
+
+impl RateLimit {
+    /// Maximum number of requests allowed in the current window.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Number of requests left in the current window.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Number of requests made in the current window.
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// When the current window ends and `remaining` resets to `limit`.
+    pub fn reset(&self) -> &DateTime<Utc> {
+        &self.reset
+    }
+
+    /// `true` if no requests are left in the current window.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    // Reads the `X-RateLimit-*` headers of a response.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.parse().ok() };
+        let reset = number("x-ratelimit-reset")?;
+
+        Some(RateLimit {
+            limit: number("x-ratelimit-limit")?,
+            remaining: number("x-ratelimit-remaining")?,
+            used: number("x-ratelimit-used").unwrap_or(0),
+            reset: Utc.timestamp_opt(reset as i64, 0).single()?,
+        })
+    }
+}
+
+impl RateLimits {
+    pub fn core(&self) -> &RateLimit {
+        &self.core
+    }
+
+    pub fn search(&self) -> &RateLimit {
+        &self.search
+    }
+
+    /// `None` if the server does not report a GraphQL limit.
+    pub fn graphql(&self) -> Option<&RateLimit> {
+        self.graphql.as_ref()
+    }
+}
+
+// When a response that was refused because a rate limit was hit can be
+// retried, or `None` if the response was not refused.
+pub(crate) fn retry_at(status: StatusCode, headers: &HeaderMap) -> Option<DateTime<Utc>> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    // Secondary rate limits say how long to wait directly.
+    let retry_after: Option<i64> = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+    if let Some(seconds) = retry_after {
+        return Some(Utc::now() + chrono::Duration::seconds(seconds));
+    }
+
+    let rate_limit = RateLimit::from_headers(headers)?;
+    if rate_limit.is_exhausted() {
+        Some(rate_limit.reset)
+    } else {
+        None
+    }
+}
+
+// How long to wait before retrying a refused response.
+pub(crate) fn retry_wait(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    let retry_at = retry_at(status, headers)?;
+    // An extra second covers the reset time being rounded down.
+    let seconds = (retry_at.timestamp() - Utc::now().timestamp()).max(0) + 1;
+    Some(Duration::from_secs(seconds as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(remaining: &str, reset: i64) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("60"));
+        headers.insert(
+            "x-ratelimit-remaining",
+            HeaderValue::from_str(remaining).unwrap(),
+        );
+        headers.insert("x-ratelimit-used", HeaderValue::from_static("60"));
+        headers.insert(
+            "x-ratelimit-reset",
+            HeaderValue::from_str(&reset.to_string()).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn parses_headers() {
+        let rate_limit = RateLimit::from_headers(&headers("12", 1372700873)).unwrap();
+
+        assert_eq!(60, rate_limit.limit());
+        assert_eq!(12, rate_limit.remaining());
+        assert_eq!(60, rate_limit.used());
+        assert_eq!(1372700873, rate_limit.reset().timestamp());
+        assert!(!rate_limit.is_exhausted());
+    }
+
+    #[test]
+    fn missing_headers() {
+        assert_eq!(None, RateLimit::from_headers(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn waits_only_when_exhausted() {
+        let reset = Utc::now().timestamp() + 30;
+
+        assert!(
+            retry_wait(StatusCode::FORBIDDEN, &headers("0", reset)).unwrap()
+                >= Duration::from_secs(30)
+        );
+        assert_eq!(
+            None,
+            retry_wait(StatusCode::FORBIDDEN, &headers("5", reset))
+        );
+        assert_eq!(None, retry_wait(StatusCode::OK, &headers("0", reset)));
+    }
+}
@@ -3,12 +3,39 @@
 use chrono::prelude::{DateTime, Utc};
 use serde::Deserialize;
 
-use crate::{Result, User};
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{RateLimit, Result, User};
+
+pub use contributors::{Contributions, Contributor};
+pub use forks::ForkSort;
+pub use languages::Languages;
+pub use pulls::{
+    Milestone, PullRequest, PullRequestCommit, PullRequestFile, PullRequestRef, Review,
+};
+pub use releases::{Release, ReleaseAsset};
+pub use stargazers::{Interval, StarHistory};
+pub use stats::{
+    CodeFrequency, CommitActivity, ContributorActivity, ContributorWeek, Participation,
+    PunchCardHour,
+};
+pub use traffic::{Period, PopularPath, Referrer, Traffic, TrafficCount};
+
+mod contributors;
+mod forks;
+mod languages;
+mod pulls;
+mod releases;
+mod stargazers;
+mod stats;
+mod traffic;
 
 /// Represents that stats of a [Github] repository.
 ///
 /// [Github]: https://github.com/
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Repo {
     id: u64,
     node_id: String,
@@ -17,22 +44,23 @@ pub struct Repo {
     private: bool,
     owner: User,
     html_url: String,
-    description: String,
+    description: Option<String>,
     fork: bool,
     url: String,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
-    pushed_at: DateTime<Utc>,
+    pushed_at: Option<DateTime<Utc>>,
     git_url: String,
     ssh_url: String,
     clone_url: String,
     svn_url: String,
-    homepage: String,
+    homepage: Option<String>,
     /// In *kilo*bytes.
     size: u64,
     stargazers_count: u64,
-    language: String,
+    language: Option<String>,
     forks_count: u64,
+    mirror_url: Option<String>,
     archived: bool,
     disabled: bool,
     has_projects: bool,
@@ -42,10 +70,27 @@ pub struct Repo {
     open_issues: u64,
     default_branch: String,
     /// Number of watchers.
-    subscribers_count: u64,
+    subscribers_count: Option<u64>,
     has_issues: bool,
     has_wiki: bool,
     open_issues_count: u64,
+    license: Option<License>,
+    parent: Option<Box<Repo>>,
+    source: Option<Box<Repo>>,
+    #[serde(skip)]
+    rate_limit: Option<RateLimit>,
+}
+
+/// A repository's license, as detected by [Github].
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct License {
+    key: String,
+    name: String,
+    spdx_id: Option<String>,
+    url: Option<String>,
+    node_id: String,
 }
 
 impl Repo {
@@ -53,13 +98,40 @@ impl Repo {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use github_stats::Repo;
     ///
     /// let repo = Repo::new("rust-lang", "rust");
     /// ```
+    #[cfg(feature = "blocking")]
     pub fn new(user: &str, repo: &str) -> Result<Self> {
-        let repo: Repo = reqwest::get(&repo_api_url(user, repo))?.json()?;
+        Repo::new_with(&Client::new()?, user, repo)
+    }
+
+    /// Creates a new `Repo` using a configured [`Client`].
+    ///
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn new_with(client: &Client, user: &str, repo: &str) -> Result<Self> {
+        let response = client.get(&repo_api_path(user, repo))?;
+        let repo = Repo {
+            rate_limit: response.rate_limit,
+            ..response.value
+        };
+
+        Ok(repo)
+    }
+
+    /// Creates a new `Repo` using an [`AsyncClient`].
+    ///
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn new_async(client: &AsyncClient, user: &str, repo: &str) -> Result<Self> {
+        let response = client.get(&repo_api_path(user, repo)).await?;
+        let repo = Repo {
+            rate_limit: response.rate_limit,
+            ..response.value
+        };
 
         Ok(repo)
     }
@@ -92,8 +164,8 @@ impl Repo {
         &self.html_url
     }
 
-    pub fn description(&self) -> &str {
-        &self.description
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
     }
 
     pub fn fork(&self) -> bool {
@@ -112,8 +184,9 @@ impl Repo {
         &self.updated_at
     }
 
-    pub fn pushed_at(&self) -> &DateTime<Utc> {
-        &self.pushed_at
+    /// `None` if the repository has never been pushed to.
+    pub fn pushed_at(&self) -> Option<&DateTime<Utc>> {
+        self.pushed_at.as_ref()
     }
 
     pub fn git_url(&self) -> &str {
@@ -132,8 +205,9 @@ impl Repo {
         &self.svn_url
     }
 
-    pub fn homepage(&self) -> &str {
-        &self.homepage
+    /// May be `Some("")` if a homepage was set and then removed.
+    pub fn homepage(&self) -> Option<&str> {
+        self.homepage.as_deref()
     }
 
     /// In *kilo*bytes.
@@ -145,14 +219,22 @@ impl Repo {
         self.stargazers_count
     }
 
-    pub fn language(&self) -> &str {
-        &self.language
+    /// The primary language, or `None` if [Github] did not detect one.
+    ///
+    /// [Github]: https://github.com/
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
     }
 
     pub fn forks_count(&self) -> u64 {
         self.forks_count
     }
 
+    /// The URL this repository mirrors, if it is a mirror.
+    pub fn mirror_url(&self) -> Option<&str> {
+        self.mirror_url.as_deref()
+    }
+
     pub fn archived(&self) -> bool {
         self.archived
     }
@@ -183,7 +265,10 @@ impl Repo {
     }
 
     /// Number of watchers.
-    pub fn subscribers_count(&self) -> u64 {
+    ///
+    /// `None` if this `Repo` was part of a list or search results, which do
+    /// not include it.
+    pub fn subscribers_count(&self) -> Option<u64> {
         self.subscribers_count
     }
 
@@ -198,12 +283,155 @@ impl Repo {
     pub fn open_issues_count(&self) -> u64 {
         self.open_issues_count
     }
+
+    /// `None` if [Github] did not detect a license.
+    ///
+    /// [Github]: https://github.com/
+    pub fn license(&self) -> Option<&License> {
+        self.license.as_ref()
+    }
+
+    /// The repository this was forked from.
+    ///
+    /// `None` if this is not a fork, or if this `Repo` was part of a list or
+    /// search results, which do not include it.
+    pub fn parent(&self) -> Option<&Repo> {
+        self.parent.as_deref()
+    }
+
+    /// The repository at the root of the fork network, which is the same as
+    /// the [`parent`] unless this is a fork of a fork.
+    ///
+    /// `None` if this is not a fork, or if this `Repo` was part of a list or
+    /// search results, which do not include it.
+    ///
+    /// [`parent`]: #method.parent
+    pub fn source(&self) -> Option<&Repo> {
+        self.source.as_deref()
+    }
+
+    /// The rate limit after fetching this `Repo`.
+    ///
+    /// `None` if the response did not report it.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
+}
+
+impl License {
+    /// Like `"mit"`, or `"other"` if the license was not recognized.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Like `"MIT"`, or `"NOASSERTION"` if the license was not recognized.
+    pub fn spdx_id(&self) -> Option<&str> {
+        self.spdx_id.as_deref()
+    }
+
+    /// API URL for the license's details.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
 }
 
-// Takes [Github] user and repo IDs to make a link to the API for that repo.
+// Takes [Github] user and repo IDs to make a path to the API for that repo.
 //
 // [Github]: https://github.com/
-fn repo_api_url(user: &str, repo: &str) -> String {
-    const URL: &str = "https://api.github.com/repos";
-    format!("{}/{}/{}", URL, user, repo)
+fn repo_api_path(user: &str, repo: &str) -> String {
+    format!("/repos/{}/{}", user, repo)
+}
+
+// The `octocat/Hello-World` fixture, for testing requests about a repository.
+#[cfg(test)]
+pub(crate) fn hello_world() -> Repo {
+    serde_json::from_str(include_str!("../tests/fixtures/repos/octocat-hello-world.json")).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(json: &str) -> Repo {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn full_repo() {
+        let repo = fixture(include_str!("../tests/fixtures/repos/rust-lang-rust.json"));
+
+        assert_eq!("rust-lang/rust", repo.full_name());
+        assert_eq!("rust-lang", repo.owner().login());
+        assert_eq!(Some("https://www.rust-lang.org"), repo.homepage());
+        assert_eq!(Some("Rust"), repo.language());
+        assert_eq!(None, repo.mirror_url());
+        assert_eq!(Some("NOASSERTION"), repo.license().unwrap().spdx_id());
+        assert_eq!(None, repo.license().unwrap().url());
+    }
+
+    #[test]
+    fn no_language_or_license() {
+        let repo = hello_world();
+
+        assert_eq!(Some("My first repository on GitHub!"), repo.description());
+        assert_eq!(Some(""), repo.homepage());
+        assert_eq!(None, repo.language());
+        assert_eq!(None, repo.license());
+    }
+
+    #[test]
+    fn empty_repo() {
+        let repo = fixture(include_str!("../tests/fixtures/repos/empty-repo.json"));
+
+        assert_eq!(None, repo.description());
+        assert_eq!(None, repo.homepage());
+        assert_eq!(None, repo.language());
+        assert_eq!(0, repo.size());
+    }
+
+    #[test]
+    fn never_pushed() {
+        let mut json: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/repos/empty-repo.json")).unwrap();
+        json["pushed_at"] = serde_json::Value::Null;
+        let repo: Repo = serde_json::from_value(json).unwrap();
+
+        assert_eq!(None, repo.pushed_at());
+    }
+
+    #[test]
+    fn mirror() {
+        let repo = fixture(include_str!("../tests/fixtures/repos/apache-mirror.json"));
+
+        assert_eq!(Some("git://git.apache.org/tomcat.git"), repo.mirror_url());
+        assert_eq!(None, repo.homepage());
+        assert_eq!("apache-2.0", repo.license().unwrap().key());
+    }
+
+    #[test]
+    fn archived_fork() {
+        let repo = fixture(include_str!("../tests/fixtures/repos/archived-fork.json"));
+
+        assert!(repo.fork());
+        assert!(repo.archived());
+        assert_eq!(Some("MIT"), repo.license().unwrap().spdx_id());
+    }
+
+    #[test]
+    fn fork_network() {
+        let repo = fixture(include_str!("../tests/fixtures/repos/monalisa-hello-world.json"));
+
+        assert!(repo.fork());
+        assert_eq!("octocat/Hello-World", repo.parent().unwrap().full_name());
+        assert_eq!("octocat/Hello-World", repo.source().unwrap().full_name());
+        assert!(repo.parent().unwrap().parent().is_none());
+    }
 }
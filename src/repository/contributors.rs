@@ -0,0 +1,346 @@
+use serde::de::{self, Deserializer};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::pagination::Paginator;
+#[cfg(feature = "async")]
+use crate::pagination::{self, PaginatedStream};
+#[cfg(feature = "blocking")]
+use crate::Client;
+#[cfg(feature = "blocking")]
+use crate::Result;
+use crate::{Repo, User};
+
+/// Someone who contributed commits to a repository.
+///
+/// Contributors whose commit emails are not linked to a [Github] user are
+/// anonymous, and only have a name and email.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Clone)]
+pub struct Contributor {
+    kind: Kind,
+    contributions: u64,
+}
+
+/// How concentrated the contributions to a repository are.
+///
+/// # Example
+///
+/// ```no_run
+/// use github_stats::{Contributions, Contributor, Repo};
+///
+/// # #[cfg(feature = "blocking")]
+/// # fn run() -> github_stats::Result<()> {
+/// let repo = Repo::new("rust-lang", "rust")?;
+/// let contributors = repo.contributors(true)?.collect::<Result<Vec<Contributor>, _>>()?;
+/// let contributions = Contributions::new(&contributors);
+///
+/// println!("Bus factor: {}", contributions.bus_factor(50.0));
+/// println!("Top 10: {:.1}%", contributions.top_percentage(10));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Contributions {
+    // Most contributions first.
+    counts: Vec<u64>,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    User(Box<User>),
+    Anonymous {
+        name: Option<String>,
+        email: Option<String>,
+    },
+}
+
+// What an anonymous contributor has instead of a user.
+#[derive(Deserialize)]
+struct Anonymous {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl Repo {
+    /// Lazily gets everyone who contributed to this repository, with the most
+    /// contributions first.
+    ///
+    /// Anonymous contributors are only included if `anonymous` is `true`.
+    /// [Github] only lists the first 500 contributor emails, and lists the
+    /// rest as anonymous.
+    ///
+    /// [Github]: https://github.com/
+    #[cfg(feature = "blocking")]
+    pub fn contributors(&self, anonymous: bool) -> Result<Paginator<Contributor>> {
+        Ok(self.contributors_with(&Client::new()?, anonymous))
+    }
+
+    /// Like [`contributors`], but uses a configured [`Client`].
+    ///
+    /// [`contributors`]: #method.contributors
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn contributors_with(&self, client: &Client, anonymous: bool) -> Paginator<Contributor> {
+        let path = contributors_api_path(self, anonymous);
+        Paginator::new::<Vec<Contributor>>(client, &path, None)
+    }
+
+    /// Like [`contributors`], but uses an [`AsyncClient`] and returns a
+    /// stream.
+    ///
+    /// [`contributors`]: #method.contributors
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn contributors_async(
+        &self,
+        client: &AsyncClient,
+        anonymous: bool,
+    ) -> PaginatedStream<Contributor> {
+        let path = contributors_api_path(self, anonymous);
+        pagination::stream::<Vec<Contributor>>(client, &path, None)
+    }
+}
+
+impl Contributor {
+    /// `None` if this contributor is anonymous.
+    pub fn user(&self) -> Option<&User> {
+        match &self.kind {
+            Kind::User(user) => Some(user.as_ref()),
+            Kind::Anonymous { .. } => None,
+        }
+    }
+
+    /// The name from an anonymous contributor's commits.
+    ///
+    /// `None` if this contributor is a [`User`], or if no name was given.
+    ///
+    /// [`User`]: struct.User.html
+    pub fn name(&self) -> Option<&str> {
+        match &self.kind {
+            Kind::User(_) => None,
+            Kind::Anonymous { name, .. } => name.as_deref(),
+        }
+    }
+
+    /// The email from an anonymous contributor's commits.
+    ///
+    /// `None` if this contributor is a [`User`], or if no email was given.
+    ///
+    /// [`User`]: struct.User.html
+    pub fn email(&self) -> Option<&str> {
+        match &self.kind {
+            Kind::User(_) => None,
+            Kind::Anonymous { email, .. } => email.as_deref(),
+        }
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        matches!(self.kind, Kind::Anonymous { .. })
+    }
+
+    /// Number of commits.
+    pub fn contributions(&self) -> u64 {
+        self.contributions
+    }
+}
+
+impl<'de> Deserialize<'de> for Contributor {
+    // Anonymous contributors have the `type` `"Anonymous"`. Everyone else must
+    // decode as a `User`, so that a malformed user is an error instead of an
+    // anonymous contributor.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let kind = if value.get("type").and_then(Value::as_str) == Some("Anonymous") {
+            let Anonymous { name, email } =
+                Anonymous::deserialize(&value).map_err(de::Error::custom)?;
+            Kind::Anonymous { name, email }
+        } else {
+            Kind::User(Box::new(
+                User::deserialize(&value).map_err(de::Error::custom)?,
+            ))
+        };
+        let contributions = match value.get("contributions") {
+            Some(contributions) => u64::deserialize(contributions).map_err(de::Error::custom)?,
+            None => return Err(de::Error::missing_field("contributions")),
+        };
+        Ok(Contributor {
+            kind,
+            contributions,
+        })
+    }
+}
+
+impl Contributions {
+    pub fn new(contributors: &[Contributor]) -> Self {
+        Contributions::from_counts(contributors.iter().map(Contributor::contributions))
+    }
+
+    /// Like [`new`], but takes the number of contributions of each
+    /// contributor.
+    ///
+    /// [`new`]: #method.new
+    pub fn from_counts(counts: impl IntoIterator<Item = u64>) -> Self {
+        let mut counts: Vec<u64> = counts.into_iter().collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        Contributions { counts }
+    }
+
+    /// Number of contributors.
+    pub fn contributors(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Number of contributions from every contributor.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// The percentage of contributions, from `0.0` to `100.0`, made by the
+    /// `n` contributors with the most contributions.
+    pub fn top_percentage(&self, n: usize) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        let top: u64 = self.counts.iter().take(n).sum();
+        top as f64 * 100.0 / total as f64
+    }
+
+    /// The fewest contributors that together made at least `percentage` of
+    /// the contributions.
+    ///
+    /// A low bus factor, like `1` for `50.0`, means that a repository relies
+    /// on a few people.
+    pub fn bus_factor(&self, percentage: f64) -> usize {
+        let total = self.total() as f64;
+        let mut sum = 0;
+        for (i, count) in self.counts.iter().enumerate() {
+            if sum as f64 * 100.0 >= percentage * total {
+                return i;
+            }
+            sum += count;
+        }
+        self.counts.len()
+    }
+}
+
+fn contributors_api_path(repo: &Repo, anonymous: bool) -> String {
+    let anon = if anonymous { "&anon=1" } else { "" };
+    format!(
+        "/repos/{}/contributors?per_page=100{}",
+        repo.full_name(),
+        anon
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contributors() {
+        let contributors: Vec<Contributor> =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/contributors.json"))
+                .unwrap();
+
+        assert_eq!("octocat", contributors[0].user().unwrap().login());
+        assert_eq!(32, contributors[0].contributions());
+        assert_eq!(None, contributors[0].name());
+        assert_eq!("Bot", contributors[1].user().unwrap().r#type());
+        assert!(contributors[2].is_anonymous());
+        assert!(contributors[2].user().is_none());
+        assert_eq!(Some("Hubot"), contributors[2].name());
+        assert_eq!(Some("hubot@example.com"), contributors[2].email());
+
+        let contributions = Contributions::new(&contributors);
+        assert_eq!(3, contributions.contributors());
+        assert_eq!(40, contributions.total());
+        assert_eq!(80.0, contributions.top_percentage(1));
+    }
+
+    #[test]
+    fn malformed_user() {
+        let json = r#"[{"id": 1, "type": "User", "contributions": 3}]"#;
+        let error = serde_path_to_error::deserialize::<_, Vec<Contributor>>(
+            &mut serde_json::Deserializer::from_str(json),
+        )
+        .unwrap_err();
+
+        assert_eq!("[0]", error.path().to_string());
+        assert!(error.inner().to_string().contains("missing field `login`"));
+    }
+
+    #[test]
+    fn concentration() {
+        let contributions = Contributions::from_counts(vec![10, 40, 5, 30, 15]);
+
+        assert_eq!(70.0, contributions.top_percentage(2));
+        assert_eq!(100.0, contributions.top_percentage(10));
+        assert_eq!(0.0, contributions.top_percentage(0));
+        assert_eq!(0, contributions.bus_factor(0.0));
+        assert_eq!(1, contributions.bus_factor(40.0));
+        assert_eq!(2, contributions.bus_factor(50.0));
+        assert_eq!(4, contributions.bus_factor(90.0));
+        assert_eq!(5, contributions.bus_factor(100.0));
+
+        let empty = Contributions::default();
+        assert_eq!(0.0, empty.top_percentage(1));
+        assert_eq!(0, empty.bus_factor(50.0));
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+
+    #[test]
+    fn requests_every_page() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                include_str!("../../tests/fixtures/repos/contributors.json"),
+            )
+            .header(
+                "Link",
+                r#"<{url}/repositories/1296269/contributors?anon=1&page=2>; rel="next""#,
+            ),
+            MockResponse::json(
+                200,
+                r#"[{"name": "Monalisa", "email": null, "type": "Anonymous", "contributions": 1}]"#,
+            ),
+        ]);
+
+        let contributors: Vec<Contributor> = hello_world()
+            .contributors_with(&server.client(), true)
+            .map(|c| c.unwrap())
+            .collect();
+        assert_eq!(4, contributors.len());
+        assert_eq!(Some("Monalisa"), contributors[3].name());
+        assert_eq!(None, contributors[3].email());
+        assert_eq!(
+            vec![
+                "GET /repos/octocat/Hello-World/contributors?per_page=100&anon=1",
+                "GET /repositories/1296269/contributors?anon=1&page=2",
+            ],
+            server.request_lines()
+        );
+    }
+
+    #[test]
+    fn empty_repository() {
+        let server = MockServer::start(vec![MockResponse::json(204, "")]);
+
+        let contributors: Vec<Result<Contributor>> = hello_world()
+            .contributors_with(&server.client(), false)
+            .collect();
+        assert!(contributors.is_empty());
+        assert_eq!(1, server.requests().len());
+    }
+}
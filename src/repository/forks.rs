@@ -0,0 +1,177 @@
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::pagination::Paginator;
+#[cfg(feature = "async")]
+use crate::pagination::{self, PaginatedStream};
+use crate::Repo;
+#[cfg(feature = "blocking")]
+use crate::{Client, Result};
+
+/// The order that forks are listed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForkSort {
+    Newest,
+    Oldest,
+    Stargazers,
+    Watchers,
+}
+
+impl Repo {
+    /// Lazily gets the forks of this repository, in the order of `sort`.
+    ///
+    /// Only direct forks are listed, not forks of forks.
+    #[cfg(feature = "blocking")]
+    pub fn forks(&self, sort: ForkSort) -> Result<Paginator<Repo>> {
+        Ok(self.forks_with(&Client::new()?, sort))
+    }
+
+    /// Like [`forks`], but uses a configured [`Client`].
+    ///
+    /// [`forks`]: #method.forks
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn forks_with(&self, client: &Client, sort: ForkSort) -> Paginator<Repo> {
+        Paginator::new::<Vec<Repo>>(client, &forks_api_path(self, sort), None)
+    }
+
+    /// Like [`forks`], but uses an [`AsyncClient`] and returns a stream.
+    ///
+    /// [`forks`]: #method.forks
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn forks_async(&self, client: &AsyncClient, sort: ForkSort) -> PaginatedStream<Repo> {
+        pagination::stream::<Vec<Repo>>(client, &forks_api_path(self, sort), None)
+    }
+
+    /// The forks in `forks` that were pushed to after this repository last
+    /// was, with the most stars first, then the most recently pushed.
+    ///
+    /// Forks that were never pushed to are not active, and if this
+    /// repository was never pushed to, every fork that was is active.
+    ///
+    /// If this repository was abandoned, the first active fork is usually
+    /// the one that carried on its development.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use github_stats::{ForkSort, Repo};
+    ///
+    /// # #[cfg(feature = "blocking")]
+    /// # fn run() -> github_stats::Result<()> {
+    /// let repo = Repo::new("octocat", "Hello-World")?;
+    /// let forks = repo.forks(ForkSort::Stargazers)?.take(500).collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// if let Some(fork) = repo.active_forks(&forks).first() {
+    ///     println!("Successor: {}", fork.full_name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn active_forks<'a>(&self, forks: &'a [Repo]) -> Vec<&'a Repo> {
+        // `None`, for never pushed to, is less than every date.
+        let mut active: Vec<&Repo> = forks
+            .iter()
+            .filter(|fork| fork.pushed_at() > self.pushed_at())
+            .collect();
+        active.sort_by(|a, b| {
+            b.stargazers_count()
+                .cmp(&a.stargazers_count())
+                .then_with(|| b.pushed_at().cmp(&a.pushed_at()))
+        });
+        active
+    }
+}
+
+impl ForkSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            ForkSort::Newest => "newest",
+            ForkSort::Oldest => "oldest",
+            ForkSort::Stargazers => "stargazers",
+            ForkSort::Watchers => "watchers",
+        }
+    }
+}
+
+fn forks_api_path(repo: &Repo, sort: ForkSort) -> String {
+    format!(
+        "/repos/{}/forks?per_page=100&sort={}",
+        repo.full_name(),
+        sort.as_str()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::hello_world;
+
+    #[test]
+    fn active_forks() {
+        let forks: Vec<Repo> =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/forks.json")).unwrap();
+
+        let active: Vec<&str> = hello_world()
+            .active_forks(&forks)
+            .into_iter()
+            .map(Repo::full_name)
+            .collect();
+        assert_eq!(
+            vec![
+                "monalisa/Hello-World",
+                "dependabot/Hello-World",
+                "hubot/Hello-World",
+            ],
+            active
+        );
+        assert!(forks[0].parent().is_none());
+    }
+
+    #[test]
+    fn never_pushed() {
+        let mut json: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/forks.json")).unwrap();
+        json[1]["pushed_at"] = serde_json::Value::Null;
+        let forks: Vec<Repo> = serde_json::from_value(json.clone()).unwrap();
+
+        let active: Vec<&str> = hello_world()
+            .active_forks(&forks)
+            .into_iter()
+            .map(Repo::full_name)
+            .collect();
+        assert_eq!(vec!["dependabot/Hello-World", "hubot/Hello-World"], active);
+
+        json[2]["pushed_at"] = serde_json::Value::Null;
+        let upstream: Repo = serde_json::from_value(json[2].clone()).unwrap();
+        assert_eq!(3, upstream.active_forks(&forks).len());
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+
+    #[test]
+    fn requests_sorted_forks() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            include_str!("../../tests/fixtures/repos/forks.json"),
+        )]);
+        let client = server.client();
+
+        let forks: Vec<Repo> = hello_world()
+            .forks_with(&client, ForkSort::Stargazers)
+            .map(|fork| fork.unwrap())
+            .collect();
+        assert_eq!(4, forks.len());
+        assert!(forks.iter().all(Repo::fork));
+        assert_eq!(
+            vec!["GET /repos/octocat/Hello-World/forks?per_page=100&sort=stargazers"],
+            server.request_lines()
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{RateLimit, Repo, Result};
+
+/// How many bytes of code a repository has in each language, as detected by
+/// [Github].
+///
+/// # Example
+///
+/// ```no_run
+/// # #[cfg(feature = "blocking")] {
+/// use github_stats::Repo;
+///
+/// if let Ok(languages) = Repo::new("rust-lang", "rust").and_then(|repo| repo.languages()) {
+///     for (language, percentage) in languages.percentages() {
+///         println!("{}: {:.1}%", language, percentage);
+///     }
+/// }
+/// # }
+/// ```
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Languages {
+    bytes: BTreeMap<String, u64>,
+    rate_limit: Option<RateLimit>,
+}
+
+impl Repo {
+    /// Gets the languages of this repository.
+    #[cfg(feature = "blocking")]
+    pub fn languages(&self) -> Result<Languages> {
+        self.languages_with(&Client::new()?)
+    }
+
+    /// Like [`languages`], but uses a configured [`Client`].
+    ///
+    /// [`languages`]: #method.languages
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn languages_with(&self, client: &Client) -> Result<Languages> {
+        let response = client.get(&languages_api_path(self))?;
+        Ok(Languages {
+            bytes: response.value,
+            rate_limit: response.rate_limit,
+        })
+    }
+
+    /// Like [`languages`], but uses an [`AsyncClient`].
+    ///
+    /// [`languages`]: #method.languages
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn languages_async(&self, client: &AsyncClient) -> Result<Languages> {
+        let response = client.get(&languages_api_path(self)).await?;
+        Ok(Languages {
+            bytes: response.value,
+            rate_limit: response.rate_limit,
+        })
+    }
+}
+
+impl Languages {
+    /// Adds up the languages of many repositories.
+    ///
+    /// ```
+    /// use github_stats::Languages;
+    ///
+    /// # let repos: Vec<Languages> = Vec::new();
+    /// let total = Languages::aggregate(&repos);
+    /// ```
+    pub fn aggregate<'a>(languages: impl IntoIterator<Item = &'a Languages>) -> Self {
+        let mut total = Languages::default();
+        for languages in languages {
+            total.merge(languages);
+        }
+        total
+    }
+
+    /// *Adds* the bytes of each language in `other`.
+    ///
+    /// The rate limit is not kept, because the result is not a response.
+    pub fn merge(&mut self, other: &Languages) {
+        for (language, bytes) in &other.bytes {
+            *self.bytes.entry(language.clone()).or_insert(0) += bytes;
+        }
+        self.rate_limit = None;
+    }
+
+    /// Bytes of code in `language`, or `None` if there is none.
+    pub fn bytes(&self, language: &str) -> Option<u64> {
+        self.bytes.get(language).copied()
+    }
+
+    /// Bytes of code in every language.
+    pub fn total_bytes(&self) -> u64 {
+        self.bytes.values().sum()
+    }
+
+    /// Each language with its bytes of code, with the most used language
+    /// first.
+    pub fn sorted(&self) -> Vec<(&str, u64)> {
+        let mut languages: Vec<(&str, u64)> = self
+            .bytes
+            .iter()
+            .map(|(language, bytes)| (language.as_str(), *bytes))
+            .collect();
+        languages.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        languages
+    }
+
+    /// The percentage of code in `language`, from `0.0` to `100.0`, or
+    /// `None` if there is none.
+    pub fn percentage(&self, language: &str) -> Option<f64> {
+        let bytes = self.bytes(language)?;
+        Some(percentage(bytes, self.total_bytes()))
+    }
+
+    /// Each language with its percentage of code, with the most used
+    /// language first.
+    pub fn percentages(&self) -> Vec<(&str, f64)> {
+        let total = self.total_bytes();
+        self.sorted()
+            .into_iter()
+            .map(|(language, bytes)| (language, percentage(bytes, total)))
+            .collect()
+    }
+
+    /// `true` if [Github] did not detect any languages.
+    ///
+    /// [Github]: https://github.com/
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The rate limit after fetching these languages.
+    ///
+    /// `None` if the response did not report it, or if these languages were
+    /// [`merge`]d.
+    ///
+    /// [`merge`]: #method.merge
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
+}
+
+fn languages_api_path(repo: &Repo) -> String {
+    format!("/repos/{}/languages", repo.full_name())
+}
+
+fn percentage(bytes: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        bytes as f64 * 100.0 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(json: &str) -> Languages {
+        Languages {
+            bytes: serde_json::from_str(json).unwrap(),
+            rate_limit: None,
+        }
+    }
+
+    #[test]
+    fn percentages() {
+        let languages = fixture(r#"{"Rust": 750, "Python": 150, "Shell": 50, "Makefile": 50}"#);
+
+        assert_eq!(1000, languages.total_bytes());
+        assert_eq!(Some(150), languages.bytes("Python"));
+        assert_eq!(None, languages.bytes("C"));
+        assert_eq!(Some(75.0), languages.percentage("Rust"));
+        assert_eq!(None, languages.percentage("C"));
+        assert_eq!(
+            vec![
+                ("Rust", 75.0),
+                ("Python", 15.0),
+                ("Makefile", 5.0),
+                ("Shell", 5.0)
+            ],
+            languages.percentages(),
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let languages = fixture("{}");
+
+        assert!(languages.is_empty());
+        assert_eq!(0, languages.total_bytes());
+        assert!(languages.percentages().is_empty());
+    }
+
+    #[test]
+    fn aggregate() {
+        let repos = vec![
+            fixture(r#"{"Rust": 300, "Shell": 100}"#),
+            fixture(r#"{"Rust": 100, "Go": 500}"#),
+            fixture("{}"),
+        ];
+        let total = Languages::aggregate(&repos);
+
+        assert_eq!(
+            vec![("Go", 500), ("Rust", 400), ("Shell", 100)],
+            total.sorted()
+        );
+
+        let mut merged = fixture(r#"{"C": 1}"#);
+        merged.merge(&total);
+        assert_eq!(Some(1), merged.bytes("C"));
+        assert_eq!(1001, merged.total_bytes());
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+
+    #[test]
+    fn requests_languages() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, r#"{"C": 78769}"#).rate_limit(60, 59)
+        ]);
+
+        let languages = hello_world().languages_with(&server.client()).unwrap();
+        assert_eq!(Some(100.0), languages.percentage("C"));
+        assert_eq!(59, languages.rate_limit().unwrap().remaining());
+        assert_eq!(
+            vec!["GET /repos/octocat/Hello-World/languages"],
+            server.request_lines()
+        );
+    }
+}
@@ -0,0 +1,826 @@
+use chrono::prelude::{DateTime, Utc};
+use serde::Deserialize;
+
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::pagination::Paginator;
+#[cfg(feature = "async")]
+use crate::pagination::{self, PaginatedStream};
+use crate::search::{CommitDetails, Label, State};
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{RateLimit, Repo, Result, User};
+
+/// A pull request to a repository.
+///
+/// The number of commits, changes and comments are only included when a
+/// single pull request is requested, and are `None` for pull requests from a
+/// list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequest {
+    id: u64,
+    node_id: String,
+    number: u64,
+    state: State,
+    locked: bool,
+    title: String,
+    user: User,
+    body: Option<String>,
+    labels: Vec<Label>,
+    milestone: Option<Milestone>,
+    assignees: Vec<User>,
+    requested_reviewers: Vec<User>,
+    #[serde(default)]
+    draft: bool,
+    head: PullRequestRef,
+    base: PullRequestRef,
+    author_association: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    merged_at: Option<DateTime<Utc>>,
+    merge_commit_sha: Option<String>,
+    merged_by: Option<User>,
+    comments: Option<u64>,
+    review_comments: Option<u64>,
+    commits: Option<u64>,
+    additions: Option<u64>,
+    deletions: Option<u64>,
+    changed_files: Option<u64>,
+    html_url: String,
+    diff_url: String,
+    patch_url: String,
+    url: String,
+    #[serde(skip)]
+    rate_limit: Option<RateLimit>,
+}
+
+/// The branch a [`PullRequest`] is merged from, or into.
+///
+/// [`PullRequest`]: struct.PullRequest.html
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestRef {
+    label: String,
+    r#ref: String,
+    sha: String,
+    user: Option<User>,
+    repo: Option<Box<Repo>>,
+}
+
+/// A group of issues and pull requests, like those planned for a release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Milestone {
+    id: u64,
+    node_id: String,
+    number: u64,
+    title: String,
+    description: Option<String>,
+    state: State,
+    creator: Option<User>,
+    open_issues: u64,
+    closed_issues: u64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    due_on: Option<DateTime<Utc>>,
+    html_url: String,
+    url: String,
+}
+
+/// A review of a [`PullRequest`].
+///
+/// [`PullRequest`]: struct.PullRequest.html
+#[derive(Debug, Clone, Deserialize)]
+pub struct Review {
+    id: u64,
+    node_id: String,
+    user: Option<User>,
+    body: String,
+    state: String,
+    commit_id: Option<String>,
+    submitted_at: Option<DateTime<Utc>>,
+    author_association: String,
+    html_url: String,
+}
+
+/// A file changed by a [`PullRequest`].
+///
+/// [`PullRequest`]: struct.PullRequest.html
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestFile {
+    sha: Option<String>,
+    filename: String,
+    previous_filename: Option<String>,
+    status: String,
+    additions: u64,
+    deletions: u64,
+    changes: u64,
+    patch: Option<String>,
+    blob_url: String,
+    raw_url: String,
+    contents_url: String,
+}
+
+/// A commit in a [`PullRequest`].
+///
+/// [`PullRequest`]: struct.PullRequest.html
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestCommit {
+    sha: String,
+    node_id: String,
+    commit: CommitDetails,
+    author: Option<User>,
+    committer: Option<User>,
+    html_url: String,
+    url: String,
+}
+
+impl Repo {
+    /// Lazily gets the pull requests to this repository in `state`, newest
+    /// first, or every pull request if `state` is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use github_stats::search::State;
+    /// use github_stats::Repo;
+    ///
+    /// # fn run() -> github_stats::Result<()> {
+    /// let repo = Repo::new("rust-lang", "rust")?;
+    ///
+    /// for pull in repo.pulls(Some(State::Open))?.take(10) {
+    ///     let pull = pull?;
+    ///     println!("#{} {}", pull.number(), pull.title());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn pulls(&self, state: Option<State>) -> Result<Paginator<PullRequest>> {
+        Ok(self.pulls_with(&Client::new()?, state))
+    }
+
+    /// Like [`pulls`], but uses a configured [`Client`].
+    ///
+    /// [`pulls`]: #method.pulls
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn pulls_with(&self, client: &Client, state: Option<State>) -> Paginator<PullRequest> {
+        Paginator::new::<Vec<PullRequest>>(client, &pulls_api_path(self, state), None)
+    }
+
+    /// Like [`pulls`], but uses an [`AsyncClient`] and returns a stream.
+    ///
+    /// [`pulls`]: #method.pulls
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn pulls_async(
+        &self,
+        client: &AsyncClient,
+        state: Option<State>,
+    ) -> PaginatedStream<PullRequest> {
+        pagination::stream::<Vec<PullRequest>>(client, &pulls_api_path(self, state), None)
+    }
+
+    /// Gets the pull request with the number `number`, including how many
+    /// commits, changes and comments it has.
+    #[cfg(feature = "blocking")]
+    pub fn pull(&self, number: u64) -> Result<PullRequest> {
+        self.pull_with(&Client::new()?, number)
+    }
+
+    /// Like [`pull`], but uses a configured [`Client`].
+    ///
+    /// [`pull`]: #method.pull
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn pull_with(&self, client: &Client, number: u64) -> Result<PullRequest> {
+        let response = client.get(&pull_api_path(self, number))?;
+        Ok(PullRequest {
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Like [`pull`], but uses an [`AsyncClient`].
+    ///
+    /// [`pull`]: #method.pull
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn pull_async(&self, client: &AsyncClient, number: u64) -> Result<PullRequest> {
+        let response = client.get(&pull_api_path(self, number)).await?;
+        Ok(PullRequest {
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Lazily gets the reviews of the pull request with the number `number`,
+    /// oldest first.
+    #[cfg(feature = "blocking")]
+    pub fn pull_reviews(&self, number: u64) -> Result<Paginator<Review>> {
+        Ok(self.pull_reviews_with(&Client::new()?, number))
+    }
+
+    /// Like [`pull_reviews`], but uses a configured [`Client`].
+    ///
+    /// [`pull_reviews`]: #method.pull_reviews
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn pull_reviews_with(&self, client: &Client, number: u64) -> Paginator<Review> {
+        let path = pull_resource_api_path(self, number, "reviews");
+        Paginator::new::<Vec<Review>>(client, &path, None)
+    }
+
+    /// Like [`pull_reviews`], but uses an [`AsyncClient`] and returns a
+    /// stream.
+    ///
+    /// [`pull_reviews`]: #method.pull_reviews
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn pull_reviews_async(&self, client: &AsyncClient, number: u64) -> PaginatedStream<Review> {
+        let path = pull_resource_api_path(self, number, "reviews");
+        pagination::stream::<Vec<Review>>(client, &path, None)
+    }
+
+    /// Lazily gets the files changed by the pull request with the number
+    /// `number`.
+    ///
+    /// [Github] only lists the first 3,000 files.
+    ///
+    /// [Github]: https://github.com/
+    #[cfg(feature = "blocking")]
+    pub fn pull_files(&self, number: u64) -> Result<Paginator<PullRequestFile>> {
+        Ok(self.pull_files_with(&Client::new()?, number))
+    }
+
+    /// Like [`pull_files`], but uses a configured [`Client`].
+    ///
+    /// [`pull_files`]: #method.pull_files
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn pull_files_with(&self, client: &Client, number: u64) -> Paginator<PullRequestFile> {
+        let path = pull_resource_api_path(self, number, "files");
+        Paginator::new::<Vec<PullRequestFile>>(client, &path, None)
+    }
+
+    /// Like [`pull_files`], but uses an [`AsyncClient`] and returns a stream.
+    ///
+    /// [`pull_files`]: #method.pull_files
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn pull_files_async(
+        &self,
+        client: &AsyncClient,
+        number: u64,
+    ) -> PaginatedStream<PullRequestFile> {
+        let path = pull_resource_api_path(self, number, "files");
+        pagination::stream::<Vec<PullRequestFile>>(client, &path, None)
+    }
+
+    /// Lazily gets the commits in the pull request with the number `number`,
+    /// oldest first.
+    ///
+    /// [Github] only lists the first 250 commits.
+    ///
+    /// [Github]: https://github.com/
+    #[cfg(feature = "blocking")]
+    pub fn pull_commits(&self, number: u64) -> Result<Paginator<PullRequestCommit>> {
+        Ok(self.pull_commits_with(&Client::new()?, number))
+    }
+
+    /// Like [`pull_commits`], but uses a configured [`Client`].
+    ///
+    /// [`pull_commits`]: #method.pull_commits
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn pull_commits_with(&self, client: &Client, number: u64) -> Paginator<PullRequestCommit> {
+        let path = pull_resource_api_path(self, number, "commits");
+        Paginator::new::<Vec<PullRequestCommit>>(client, &path, None)
+    }
+
+    /// Like [`pull_commits`], but uses an [`AsyncClient`] and returns a
+    /// stream.
+    ///
+    /// [`pull_commits`]: #method.pull_commits
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn pull_commits_async(
+        &self,
+        client: &AsyncClient,
+        number: u64,
+    ) -> PaginatedStream<PullRequestCommit> {
+        let path = pull_resource_api_path(self, number, "commits");
+        pagination::stream::<Vec<PullRequestCommit>>(client, &path, None)
+    }
+}
+
+impl PullRequest {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// Merged pull requests are `Closed`.
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The author.
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn milestone(&self) -> Option<&Milestone> {
+        self.milestone.as_ref()
+    }
+
+    pub fn assignees(&self) -> &[User] {
+        &self.assignees
+    }
+
+    /// The users whose review was requested and who have not reviewed yet.
+    pub fn requested_reviewers(&self) -> &[User] {
+        &self.requested_reviewers
+    }
+
+    pub fn draft(&self) -> bool {
+        self.draft
+    }
+
+    /// The branch with the changes.
+    pub fn head(&self) -> &PullRequestRef {
+        &self.head
+    }
+
+    /// The branch the changes are merged into.
+    pub fn base(&self) -> &PullRequestRef {
+        &self.base
+    }
+
+    /// Like `"OWNER"`, `"CONTRIBUTOR"` or `"NONE"`.
+    pub fn author_association(&self) -> &str {
+        &self.author_association
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    /// `None` if the pull request is open.
+    pub fn closed_at(&self) -> Option<&DateTime<Utc>> {
+        self.closed_at.as_ref()
+    }
+
+    pub fn merged(&self) -> bool {
+        self.merged_at.is_some()
+    }
+
+    /// `None` if the pull request has not been merged.
+    pub fn merged_at(&self) -> Option<&DateTime<Utc>> {
+        self.merged_at.as_ref()
+    }
+
+    /// The commit that merged the pull request, or the test merge commit if
+    /// it has not been merged.
+    pub fn merge_commit_sha(&self) -> Option<&str> {
+        self.merge_commit_sha.as_deref()
+    }
+
+    /// `None` if the pull request has not been merged, or if it was part of
+    /// a list.
+    pub fn merged_by(&self) -> Option<&User> {
+        self.merged_by.as_ref()
+    }
+
+    /// Number of comments on the pull request itself.
+    pub fn comments(&self) -> Option<u64> {
+        self.comments
+    }
+
+    /// Number of comments on the changes.
+    pub fn review_comments(&self) -> Option<u64> {
+        self.review_comments
+    }
+
+    pub fn commits(&self) -> Option<u64> {
+        self.commits
+    }
+
+    /// Number of lines added.
+    pub fn additions(&self) -> Option<u64> {
+        self.additions
+    }
+
+    /// Number of lines deleted.
+    pub fn deletions(&self) -> Option<u64> {
+        self.deletions
+    }
+
+    pub fn changed_files(&self) -> Option<u64> {
+        self.changed_files
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn diff_url(&self) -> &str {
+        &self.diff_url
+    }
+
+    pub fn patch_url(&self) -> &str {
+        &self.patch_url
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The rate limit after fetching this `PullRequest`.
+    ///
+    /// `None` if the response did not report it, or if this `PullRequest`
+    /// was part of a list.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
+}
+
+impl PullRequestRef {
+    /// Like `"octocat:main"`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The branch name, like `"main"`.
+    pub fn r#ref(&self) -> &str {
+        &self.r#ref
+    }
+
+    /// The commit the branch pointed to.
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// `None` if the user was deleted.
+    pub fn user(&self) -> Option<&User> {
+        self.user.as_ref()
+    }
+
+    /// `None` if the repository was deleted, like a fork after its pull
+    /// request was merged.
+    pub fn repo(&self) -> Option<&Repo> {
+        self.repo.as_deref()
+    }
+}
+
+impl Milestone {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn creator(&self) -> Option<&User> {
+        self.creator.as_ref()
+    }
+
+    pub fn open_issues(&self) -> u64 {
+        self.open_issues
+    }
+
+    pub fn closed_issues(&self) -> u64 {
+        self.closed_issues
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn closed_at(&self) -> Option<&DateTime<Utc>> {
+        self.closed_at.as_ref()
+    }
+
+    pub fn due_on(&self) -> Option<&DateTime<Utc>> {
+        self.due_on.as_ref()
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Review {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// The reviewer, or `None` if they were deleted.
+    pub fn user(&self) -> Option<&User> {
+        self.user.as_ref()
+    }
+
+    /// Empty if the review has no summary.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Like `"APPROVED"`, `"CHANGES_REQUESTED"`, `"COMMENTED"`,
+    /// `"DISMISSED"` or `"PENDING"`.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// The commit that was reviewed.
+    pub fn commit_id(&self) -> Option<&str> {
+        self.commit_id.as_deref()
+    }
+
+    /// `None` if the review is pending.
+    pub fn submitted_at(&self) -> Option<&DateTime<Utc>> {
+        self.submitted_at.as_ref()
+    }
+
+    pub fn author_association(&self) -> &str {
+        &self.author_association
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+}
+
+impl PullRequestFile {
+    /// The file's blob SHA.
+    pub fn sha(&self) -> Option<&str> {
+        self.sha.as_deref()
+    }
+
+    /// The file's path in the repository.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// The file's path before it was renamed.
+    pub fn previous_filename(&self) -> Option<&str> {
+        self.previous_filename.as_deref()
+    }
+
+    /// Like `"added"`, `"removed"`, `"modified"` or `"renamed"`.
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Number of lines added.
+    pub fn additions(&self) -> u64 {
+        self.additions
+    }
+
+    /// Number of lines deleted.
+    pub fn deletions(&self) -> u64 {
+        self.deletions
+    }
+
+    /// Number of lines added and deleted.
+    pub fn changes(&self) -> u64 {
+        self.changes
+    }
+
+    /// `None` for binary files, and for diffs that are too large.
+    pub fn patch(&self) -> Option<&str> {
+        self.patch.as_deref()
+    }
+
+    pub fn blob_url(&self) -> &str {
+        &self.blob_url
+    }
+
+    pub fn raw_url(&self) -> &str {
+        &self.raw_url
+    }
+
+    pub fn contents_url(&self) -> &str {
+        &self.contents_url
+    }
+}
+
+impl PullRequestCommit {
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn commit(&self) -> &CommitDetails {
+        &self.commit
+    }
+
+    /// `None` if the author's email is not linked to a [Github] user.
+    ///
+    /// [Github]: https://github.com/
+    pub fn author(&self) -> Option<&User> {
+        self.author.as_ref()
+    }
+
+    /// `None` if the committer's email is not linked to a [Github] user.
+    ///
+    /// [Github]: https://github.com/
+    pub fn committer(&self) -> Option<&User> {
+        self.committer.as_ref()
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+fn pulls_api_path(repo: &Repo, state: Option<State>) -> String {
+    format!(
+        "/repos/{}/pulls?per_page=100&state={}",
+        repo.full_name(),
+        state.map_or("all", State::as_str)
+    )
+}
+
+fn pull_api_path(repo: &Repo, number: u64) -> String {
+    format!("/repos/{}/pulls/{}", repo.full_name(), number)
+}
+
+// Like `/pulls/1347/reviews`, for one of a pull request's lists.
+fn pull_resource_api_path(repo: &Repo, number: u64, resource: &str) -> String {
+    format!("{}/{}?per_page=100", pull_api_path(repo, number), resource)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pulls() {
+        let pulls: Vec<PullRequest> =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/pulls.json")).unwrap();
+
+        assert_eq!(State::Open, pulls[0].state());
+        assert!(pulls[0].draft());
+        assert!(!pulls[0].merged());
+        assert_eq!(None, pulls[0].milestone().map(Milestone::title));
+        assert_eq!(None, pulls[0].additions());
+        assert_eq!("wip", pulls[0].head().r#ref());
+        assert!(pulls[1].merged());
+        assert!(pulls[1].head().repo().is_none());
+        assert_eq!(
+            "octocat/Hello-World",
+            pulls[1].base().repo().unwrap().full_name()
+        );
+    }
+
+    #[test]
+    fn pull() {
+        let pull: PullRequest =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/pull-request.json"))
+                .unwrap();
+
+        assert_eq!(1347, pull.number());
+        assert_eq!(State::Closed, pull.state());
+        assert!(pull.merged());
+        assert_eq!("octocat", pull.merged_by().unwrap().login());
+        assert_eq!(Some(3), pull.commits());
+        assert_eq!(Some(100), pull.additions());
+        assert_eq!(Some(3), pull.deletions());
+        assert_eq!(Some(5), pull.changed_files());
+        assert_eq!("bug", pull.labels()[0].name());
+        assert_eq!("v1.0", pull.milestone().unwrap().title());
+        assert_eq!(State::Open, pull.milestone().unwrap().state());
+        assert_eq!("monalisa", pull.requested_reviewers()[0].login());
+        assert_eq!("hubot:new-topic", pull.head().label());
+    }
+
+    #[test]
+    fn sub_resources() {
+        let reviews: Vec<Review> =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/pull-reviews.json"))
+                .unwrap();
+        assert_eq!("APPROVED", reviews[0].state());
+        assert!(reviews[1].user().is_none());
+        assert_eq!("", reviews[1].body());
+
+        let files: Vec<PullRequestFile> =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/pull-files.json"))
+                .unwrap();
+        assert_eq!(124, files[0].changes());
+        assert_eq!(Some("logo.png"), files[1].previous_filename());
+        assert_eq!(None, files[1].patch());
+
+        let commits: Vec<PullRequestCommit> =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/pull-commits.json"))
+                .unwrap();
+        assert_eq!("Fix all the bugs", commits[0].commit().message());
+        assert_eq!("hubot", commits[0].author().unwrap().login());
+        assert!(commits[0].committer().is_none());
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+
+    #[test]
+    fn requests() {
+        let repo = hello_world();
+        let server = MockServer::start(vec![
+            MockResponse::json(200, include_str!("../../tests/fixtures/repos/pulls.json")),
+            MockResponse::json(
+                200,
+                include_str!("../../tests/fixtures/repos/pull-request.json"),
+            ),
+            MockResponse::json(200, "[]"),
+            MockResponse::json(200, "[]"),
+            MockResponse::json(200, "[]"),
+        ]);
+        let client = server.client();
+
+        assert_eq!(2, repo.pulls_with(&client, Some(State::Closed)).count());
+        let pull = repo.pull_with(&client, 1347).unwrap();
+        assert_eq!(Some(10), pull.comments());
+        assert_eq!(0, repo.pull_reviews_with(&client, 1347).count());
+        assert_eq!(0, repo.pull_files_with(&client, 1347).count());
+        assert_eq!(0, repo.pull_commits_with(&client, 1347).count());
+
+        assert_eq!(
+            vec![
+                "GET /repos/octocat/Hello-World/pulls?per_page=100&state=closed",
+                "GET /repos/octocat/Hello-World/pulls/1347",
+                "GET /repos/octocat/Hello-World/pulls/1347/reviews?per_page=100",
+                "GET /repos/octocat/Hello-World/pulls/1347/files?per_page=100",
+                "GET /repos/octocat/Hello-World/pulls/1347/commits?per_page=100",
+            ],
+            server.request_lines()
+        );
+    }
+}
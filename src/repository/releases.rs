@@ -0,0 +1,457 @@
+use chrono::prelude::{DateTime, Utc};
+#[cfg(feature = "async")]
+use futures_util::StreamExt;
+use serde::Deserialize;
+
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::pagination::Paginator;
+#[cfg(feature = "async")]
+use crate::pagination::{self, PaginatedStream};
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{RateLimit, Repo, Result, User};
+
+/// A release of a repository, and the files attached to it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    id: u64,
+    node_id: String,
+    tag_name: String,
+    target_commitish: String,
+    name: Option<String>,
+    body: Option<String>,
+    draft: bool,
+    prerelease: bool,
+    created_at: DateTime<Utc>,
+    published_at: Option<DateTime<Utc>>,
+    author: User,
+    assets: Vec<ReleaseAsset>,
+    html_url: String,
+    url: String,
+    tarball_url: Option<String>,
+    zipball_url: Option<String>,
+    #[serde(skip)]
+    rate_limit: Option<RateLimit>,
+}
+
+/// A file attached to a [`Release`].
+///
+/// [`Release`]: struct.Release.html
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    id: u64,
+    node_id: String,
+    name: String,
+    label: Option<String>,
+    state: String,
+    content_type: String,
+    size: u64,
+    download_count: u64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    uploader: Option<User>,
+    browser_download_url: String,
+    url: String,
+}
+
+impl Repo {
+    /// Lazily gets every release of this repository, newest first.
+    ///
+    /// Draft releases are only included if the client is authenticated as
+    /// someone with push access to the repository.
+    #[cfg(feature = "blocking")]
+    pub fn releases(&self) -> Result<Paginator<Release>> {
+        Ok(self.releases_with(&Client::new()?))
+    }
+
+    /// Like [`releases`], but uses a configured [`Client`].
+    ///
+    /// [`releases`]: #method.releases
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn releases_with(&self, client: &Client) -> Paginator<Release> {
+        Paginator::new::<Vec<Release>>(client, &releases_api_path(self), None)
+    }
+
+    /// Like [`releases`], but uses an [`AsyncClient`] and returns a stream.
+    ///
+    /// [`releases`]: #method.releases
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn releases_async(&self, client: &AsyncClient) -> PaginatedStream<Release> {
+        pagination::stream::<Vec<Release>>(client, &releases_api_path(self), None)
+    }
+
+    /// Gets the latest published full release, which is not a draft or a
+    /// prerelease.
+    #[cfg(feature = "blocking")]
+    pub fn latest_release(&self) -> Result<Release> {
+        self.latest_release_with(&Client::new()?)
+    }
+
+    /// Like [`latest_release`], but uses a configured [`Client`].
+    ///
+    /// [`latest_release`]: #method.latest_release
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn latest_release_with(&self, client: &Client) -> Result<Release> {
+        let response = client.get(&release_api_path(self, "latest"))?;
+        Ok(Release {
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Like [`latest_release`], but uses an [`AsyncClient`].
+    ///
+    /// [`latest_release`]: #method.latest_release
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn latest_release_async(&self, client: &AsyncClient) -> Result<Release> {
+        let response = client.get(&release_api_path(self, "latest")).await?;
+        Ok(Release {
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Gets the published release with the tag `tag`, like `"v1.0.0"`.
+    #[cfg(feature = "blocking")]
+    pub fn release_by_tag(&self, tag: &str) -> Result<Release> {
+        self.release_by_tag_with(&Client::new()?, tag)
+    }
+
+    /// Like [`release_by_tag`], but uses a configured [`Client`].
+    ///
+    /// [`release_by_tag`]: #method.release_by_tag
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn release_by_tag_with(&self, client: &Client, tag: &str) -> Result<Release> {
+        let response = client.get(&release_api_path(self, &tag_segment(tag)))?;
+        Ok(Release {
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Like [`release_by_tag`], but uses an [`AsyncClient`].
+    ///
+    /// [`release_by_tag`]: #method.release_by_tag
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn release_by_tag_async(&self, client: &AsyncClient, tag: &str) -> Result<Release> {
+        let path = release_api_path(self, &tag_segment(tag));
+        let response = client.get(&path).await?;
+        Ok(Release {
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Gets the number of times every asset of every release has been
+    /// downloaded.
+    ///
+    /// This requests every page of [`releases`].
+    ///
+    /// [`releases`]: #method.releases
+    #[cfg(feature = "blocking")]
+    pub fn download_count(&self) -> Result<u64> {
+        self.download_count_with(&Client::new()?)
+    }
+
+    /// Like [`download_count`], but uses a configured [`Client`].
+    ///
+    /// [`download_count`]: #method.download_count
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn download_count_with(&self, client: &Client) -> Result<u64> {
+        let mut total = 0;
+        for release in self.releases_with(client) {
+            total += release?.download_count();
+        }
+        Ok(total)
+    }
+
+    /// Like [`download_count`], but uses an [`AsyncClient`].
+    ///
+    /// [`download_count`]: #method.download_count
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn download_count_async(&self, client: &AsyncClient) -> Result<u64> {
+        let mut releases = self.releases_async(client);
+        let mut total = 0;
+        while let Some(release) = releases.next().await {
+            total += release?.download_count();
+        }
+        Ok(total)
+    }
+}
+
+impl Release {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn tag_name(&self) -> &str {
+        &self.tag_name
+    }
+
+    /// The branch or commit the tag was created from.
+    pub fn target_commitish(&self) -> &str {
+        &self.target_commitish
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    pub fn draft(&self) -> bool {
+        self.draft
+    }
+
+    pub fn prerelease(&self) -> bool {
+        self.prerelease
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    /// `None` if this is a draft.
+    pub fn published_at(&self) -> Option<&DateTime<Utc>> {
+        self.published_at.as_ref()
+    }
+
+    pub fn author(&self) -> &User {
+        &self.author
+    }
+
+    pub fn assets(&self) -> &[ReleaseAsset] {
+        &self.assets
+    }
+
+    /// The number of times every asset has been downloaded.
+    ///
+    /// The source code archives are not assets, so their downloads are not
+    /// counted.
+    pub fn download_count(&self) -> u64 {
+        self.assets.iter().map(ReleaseAsset::download_count).sum()
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn tarball_url(&self) -> Option<&str> {
+        self.tarball_url.as_deref()
+    }
+
+    pub fn zipball_url(&self) -> Option<&str> {
+        self.zipball_url.as_deref()
+    }
+
+    /// The rate limit after fetching this `Release`.
+    ///
+    /// `None` if the response did not report it, or if this `Release` was
+    /// part of a list.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
+}
+
+impl ReleaseAsset {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// The file name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A short description shown instead of the name.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// `"uploaded"` or `"open"`.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// In bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn download_count(&self) -> u64 {
+        self.download_count
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn uploader(&self) -> Option<&User> {
+        self.uploader.as_ref()
+    }
+
+    /// Where the file is downloaded from.
+    pub fn browser_download_url(&self) -> &str {
+        &self.browser_download_url
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+fn releases_api_path(repo: &Repo) -> String {
+    format!("/repos/{}/releases?per_page=100", repo.full_name())
+}
+
+fn release_api_path(repo: &Repo, release: &str) -> String {
+    format!("/repos/{}/releases/{}", repo.full_name(), release)
+}
+
+// Like `tags/v1.0%23rc`, with `tag` percent-encoded so that characters like
+// `/`, `#` and `?` stay part of it.
+fn tag_segment(tag: &str) -> String {
+    // Spaces are encoded as `+` in forms, but not in paths. A literal `+` is
+    // already encoded as `%2B`.
+    let tag: String = form_urlencoded::byte_serialize(tag.as_bytes()).collect();
+    format!("tags/{}", tag.replace('+', "%20"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn releases() {
+        let releases: Vec<Release> =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/releases.json")).unwrap();
+
+        assert!(releases[0].prerelease());
+        assert_eq!(None, releases[0].name());
+        assert_eq!(0, releases[0].download_count());
+        assert_eq!("v1.0.0", releases[1].tag_name());
+        assert_eq!(Some("short description"), releases[1].assets()[0].label());
+        assert!(releases[1].assets()[1].uploader().is_none());
+        assert_eq!(50, releases[1].download_count());
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+
+    fn release(json: &str) -> String {
+        let releases: serde_json::Value = serde_json::from_str(json).unwrap();
+        releases[1].to_string()
+    }
+
+    #[test]
+    fn download_count_of_every_page() {
+        let releases = include_str!("../../tests/fixtures/repos/releases.json");
+        let server = MockServer::start(vec![
+            MockResponse::json(200, releases).header(
+                "Link",
+                r#"<{url}/repositories/1296269/releases?per_page=100&page=2>; rel="next""#,
+            ),
+            MockResponse::json(200, releases),
+        ]);
+        let client = server.client();
+
+        assert_eq!(100, hello_world().download_count_with(&client).unwrap());
+        assert_eq!(
+            vec![
+                "GET /repos/octocat/Hello-World/releases?per_page=100",
+                "GET /repositories/1296269/releases?per_page=100&page=2",
+            ],
+            server.request_lines()
+        );
+    }
+
+    #[test]
+    fn latest_and_by_tag() {
+        let releases = include_str!("../../tests/fixtures/repos/releases.json");
+        let server = MockServer::start(vec![
+            MockResponse::json(200, &release(releases)),
+            MockResponse::json(200, &release(releases)),
+        ]);
+        let client = server.client();
+
+        assert_eq!(
+            "v1.0.0",
+            hello_world()
+                .latest_release_with(&client)
+                .unwrap()
+                .tag_name()
+        );
+        let release = hello_world()
+            .release_by_tag_with(&client, "v1.0.0")
+            .unwrap();
+        assert_eq!(50, release.download_count());
+
+        assert_eq!(
+            vec![
+                "GET /repos/octocat/Hello-World/releases/latest",
+                "GET /repos/octocat/Hello-World/releases/tags/v1.0.0",
+            ],
+            server.request_lines()
+        );
+    }
+
+    #[test]
+    fn encodes_tag() {
+        let releases = include_str!("../../tests/fixtures/repos/releases.json");
+        let server = MockServer::start(vec![
+            MockResponse::json(200, &release(releases)),
+            MockResponse::json(200, &release(releases)),
+        ]);
+        let client = server.client();
+
+        hello_world()
+            .release_by_tag_with(&client, "v1.0#rc")
+            .unwrap();
+        hello_world()
+            .release_by_tag_with(&client, "nightly/2020 05?a+b%")
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                "GET /repos/octocat/Hello-World/releases/tags/v1.0%23rc",
+                "GET /repos/octocat/Hello-World/releases/tags/nightly%2F2020%2005%3Fa%2Bb%25",
+            ],
+            server.request_lines()
+        );
+    }
+}
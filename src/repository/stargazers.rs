@@ -0,0 +1,285 @@
+use chrono::prelude::{DateTime, Datelike, NaiveDate, Utc};
+use chrono::{Days, Months};
+use serde::Deserialize;
+
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+use crate::pagination::Page;
+#[cfg(feature = "blocking")]
+use crate::pagination::Paginator;
+#[cfg(feature = "async")]
+use crate::pagination::{self, PaginatedStream};
+#[cfg(feature = "blocking")]
+use crate::Client;
+#[cfg(feature = "blocking")]
+use crate::Result;
+use crate::{Repo, User};
+
+// Makes Github include when each star was given.
+const STAR_MEDIA_TYPE: &str = "application/vnd.github.star+json";
+
+/// How a [`StarHistory`] is grouped.
+///
+/// Weeks start on Monday.
+///
+/// [`StarHistory`]: struct.StarHistory.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interval {
+    Day,
+    Week,
+    Month,
+}
+
+/// How many stars a repository had over time.
+///
+/// # Example
+///
+/// ```no_run
+/// use github_stats::{Interval, Repo, StarHistory};
+///
+/// # #[cfg(feature = "blocking")]
+/// # fn run() -> github_stats::Result<()> {
+/// let repo = Repo::new("rust-lang", "rust")?;
+/// let stars = repo.stargazers()?.map(|star| star.map(|(_, starred_at)| starred_at));
+/// let history = StarHistory::from_dates(stars.collect::<Result<Vec<_>, _>>()?);
+///
+/// for (month, stars) in history.growth(Interval::Month) {
+///     println!("{}: {}", month.format("%Y-%m"), stars);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StarHistory {
+    // Oldest first.
+    starred_at: Vec<DateTime<Utc>>,
+}
+
+// A star, as returned with the star media type.
+#[derive(Deserialize)]
+struct Star {
+    starred_at: DateTime<Utc>,
+    user: User,
+}
+
+// A page of stars, which are listed as who starred when.
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct Stars(Vec<Star>);
+
+impl Page for Stars {
+    type Item = (User, DateTime<Utc>);
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.0
+            .into_iter()
+            .map(|star| (star.user, star.starred_at))
+            .collect()
+    }
+}
+
+impl Repo {
+    /// Lazily gets everyone who starred this repository, and when they did,
+    /// oldest first.
+    ///
+    /// [Github] only lists the first 40,000 stargazers.
+    ///
+    /// [Github]: https://github.com/
+    #[cfg(feature = "blocking")]
+    pub fn stargazers(&self) -> Result<Paginator<(User, DateTime<Utc>)>> {
+        Ok(self.stargazers_with(&Client::new()?))
+    }
+
+    /// Like [`stargazers`], but uses a configured [`Client`].
+    ///
+    /// [`stargazers`]: #method.stargazers
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn stargazers_with(&self, client: &Client) -> Paginator<(User, DateTime<Utc>)> {
+        Paginator::new::<Stars>(client, &stargazers_api_path(self), None).accept(STAR_MEDIA_TYPE)
+    }
+
+    /// Like [`stargazers`], but uses an [`AsyncClient`] and returns a stream.
+    ///
+    /// [`stargazers`]: #method.stargazers
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn stargazers_async(&self, client: &AsyncClient) -> PaginatedStream<(User, DateTime<Utc>)> {
+        let path = stargazers_api_path(self);
+        pagination::stream_as::<Stars>(client, &path, None, Some(STAR_MEDIA_TYPE))
+    }
+}
+
+impl Interval {
+    // The first day of the interval that `date` is in.
+    fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Interval::Day => date,
+            Interval::Week => date - Days::new(date.weekday().num_days_from_monday().into()),
+            Interval::Month => date - Days::new(date.day0().into()),
+        }
+    }
+
+    // The first day of the interval after the one starting on `start`.
+    fn next(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Interval::Day => start + Days::new(1),
+            Interval::Week => start + Days::new(7),
+            Interval::Month => start + Months::new(1),
+        }
+    }
+}
+
+impl StarHistory {
+    pub fn new(stargazers: &[(User, DateTime<Utc>)]) -> Self {
+        StarHistory::from_dates(stargazers.iter().map(|(_, starred_at)| *starred_at))
+    }
+
+    /// Like [`new`], but takes when each star was given.
+    ///
+    /// [`new`]: #method.new
+    pub fn from_dates(starred_at: impl IntoIterator<Item = DateTime<Utc>>) -> Self {
+        let mut starred_at: Vec<DateTime<Utc>> = starred_at.into_iter().collect();
+        starred_at.sort_unstable();
+        StarHistory { starred_at }
+    }
+
+    /// Number of stars.
+    pub fn total(&self) -> usize {
+        self.starred_at.len()
+    }
+
+    /// The total number of stars at the end of each interval, from the
+    /// interval of the first star to the interval of the last star.
+    ///
+    /// Each interval is keyed by its first day, in UTC. Intervals without
+    /// new stars are included, so the series has no gaps.
+    pub fn growth(&self, interval: Interval) -> Vec<(NaiveDate, u64)> {
+        let (first, last) = match (self.starred_at.first(), self.starred_at.last()) {
+            (Some(first), Some(last)) => (first.date_naive(), last.date_naive()),
+            _ => return Vec::new(),
+        };
+        let mut dates = self.starred_at.iter().map(DateTime::date_naive).peekable();
+        let mut series = Vec::new();
+        let mut total = 0;
+        let mut start = interval.start(first);
+        while start <= last {
+            let next = interval.next(start);
+            while dates.next_if(|date| *date < next).is_some() {
+                total += 1;
+            }
+            series.push((start, total));
+            start = next;
+        }
+        series
+    }
+}
+
+fn stargazers_api_path(repo: &Repo) -> String {
+    format!("/repos/{}/stargazers?per_page=100", repo.full_name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn stargazers() {
+        let stars: Stars =
+            serde_json::from_str(include_str!("../../tests/fixtures/repos/stargazers.json"))
+                .unwrap();
+        let stargazers = stars.into_items();
+
+        assert_eq!(3, stargazers.len());
+        assert_eq!("octocat", stargazers[0].0.login());
+        assert_eq!("2011-01-26T19:14:43+00:00", stargazers[0].1.to_rfc3339());
+
+        let history = StarHistory::new(&stargazers);
+        assert_eq!(3, history.total());
+        assert_eq!(
+            vec![
+                (date(2011, 1, 1), 2),
+                (date(2011, 2, 1), 2),
+                (date(2011, 3, 1), 3)
+            ],
+            history.growth(Interval::Month)
+        );
+    }
+
+    #[test]
+    fn growth() {
+        let history = StarHistory::from_dates(
+            [
+                "2020-01-08T00:00:00Z",
+                "2020-01-01T12:00:00Z",
+                "2020-01-01T23:59:59Z",
+                "2020-01-03T00:00:00Z",
+            ]
+            .iter()
+            .map(|date| date.parse().unwrap()),
+        );
+
+        assert_eq!(
+            vec![
+                (date(2020, 1, 1), 2),
+                (date(2020, 1, 2), 2),
+                (date(2020, 1, 3), 3),
+                (date(2020, 1, 4), 3),
+                (date(2020, 1, 5), 3),
+                (date(2020, 1, 6), 3),
+                (date(2020, 1, 7), 3),
+                (date(2020, 1, 8), 4),
+            ],
+            history.growth(Interval::Day)
+        );
+        assert_eq!(
+            vec![(date(2019, 12, 30), 3), (date(2020, 1, 6), 4)],
+            history.growth(Interval::Week)
+        );
+        assert_eq!(vec![(date(2020, 1, 1), 4)], history.growth(Interval::Month));
+        assert!(StarHistory::default().growth(Interval::Day).is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+
+    #[test]
+    fn requests_stars() {
+        let repo = hello_world();
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                include_str!("../../tests/fixtures/repos/stargazers.json"),
+            )
+            .header(
+                "Link",
+                r#"<{url}/repositories/1296269/stargazers?per_page=100&page=2>; rel="next""#,
+            ),
+            MockResponse::json(200, "[]"),
+        ]);
+        let client = server.client();
+
+        let stargazers: Vec<(User, DateTime<Utc>)> =
+            repo.stargazers_with(&client).map(|s| s.unwrap()).collect();
+        assert_eq!(3, stargazers.len());
+        assert_eq!("monalisa", stargazers[2].0.login());
+
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(
+            "GET /repos/octocat/Hello-World/stargazers?per_page=100",
+            requests[0].line
+        );
+        for request in &requests {
+            assert_eq!(Some(STAR_MEDIA_TYPE), request.header("accept"));
+        }
+    }
+}
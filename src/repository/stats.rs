@@ -0,0 +1,535 @@
+use chrono::prelude::{DateTime, Utc};
+use serde::Deserialize;
+
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{List, RateLimit, Repo, Result, User};
+
+/// A contributor's weekly additions, deletions and commits.
+///
+/// [Github] only computes these for repositories with fewer than 10,000
+/// commits.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContributorActivity {
+    author: Option<User>,
+    total: u64,
+    weeks: Vec<ContributorWeek>,
+}
+
+/// One week of a [`ContributorActivity`].
+///
+/// [`ContributorActivity`]: struct.ContributorActivity.html
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ContributorWeek {
+    #[serde(rename = "w", with = "chrono::serde::ts_seconds")]
+    week: DateTime<Utc>,
+    #[serde(rename = "a")]
+    additions: u64,
+    #[serde(rename = "d")]
+    deletions: u64,
+    #[serde(rename = "c")]
+    commits: u64,
+}
+
+/// The number of commits on each day of one week.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CommitActivity {
+    days: Vec<u64>,
+    total: u64,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    week: DateTime<Utc>,
+}
+
+/// The number of lines added and deleted in one week.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CodeFrequency(
+    #[serde(with = "chrono::serde::ts_seconds")] DateTime<Utc>,
+    i64,
+    i64,
+);
+
+/// The number of commits in each of the last 52 weeks, by everyone and by
+/// the repository's owner.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Participation {
+    all: Vec<u64>,
+    owner: Vec<u64>,
+    #[serde(skip)]
+    rate_limit: Option<RateLimit>,
+}
+
+/// The number of commits in one hour of the week, for every week.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PunchCardHour(u8, u8, u64);
+
+impl Repo {
+    /// Gets the weekly activity of each contributor.
+    ///
+    /// [Github] computes statistics in the background, so this retries until
+    /// they are ready, or fails with [`Error::NotReady`]. See
+    /// [`ClientBuilder::stats_backoff`].
+    ///
+    /// [Github]: https://github.com/
+    /// [`Error::NotReady`]: enum.Error.html#variant.NotReady
+    /// [`ClientBuilder::stats_backoff`]: struct.ClientBuilder.html#method.stats_backoff
+    #[cfg(feature = "blocking")]
+    pub fn contributor_activity(&self) -> Result<List<ContributorActivity>> {
+        self.contributor_activity_with(&Client::new()?)
+    }
+
+    /// Like [`contributor_activity`], but uses a configured [`Client`].
+    ///
+    /// [`contributor_activity`]: #method.contributor_activity
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn contributor_activity_with(&self, client: &Client) -> Result<List<ContributorActivity>> {
+        let path = stats_api_path(self, "contributors");
+        Ok(List::from_response(client.get_stats(&path)?))
+    }
+
+    /// Like [`contributor_activity`], but uses an [`AsyncClient`].
+    ///
+    /// [`contributor_activity`]: #method.contributor_activity
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn contributor_activity_async(
+        &self,
+        client: &AsyncClient,
+    ) -> Result<List<ContributorActivity>> {
+        let path = stats_api_path(self, "contributors");
+        Ok(List::from_response(client.get_stats(&path).await?))
+    }
+
+    /// Gets the daily commits of the last year, grouped by week.
+    ///
+    /// Retries like [`contributor_activity`].
+    ///
+    /// [`contributor_activity`]: #method.contributor_activity
+    #[cfg(feature = "blocking")]
+    pub fn commit_activity(&self) -> Result<List<CommitActivity>> {
+        self.commit_activity_with(&Client::new()?)
+    }
+
+    /// Like [`commit_activity`], but uses a configured [`Client`].
+    ///
+    /// [`commit_activity`]: #method.commit_activity
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn commit_activity_with(&self, client: &Client) -> Result<List<CommitActivity>> {
+        let path = stats_api_path(self, "commit_activity");
+        Ok(List::from_response(client.get_stats(&path)?))
+    }
+
+    /// Like [`commit_activity`], but uses an [`AsyncClient`].
+    ///
+    /// [`commit_activity`]: #method.commit_activity
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn commit_activity_async(
+        &self,
+        client: &AsyncClient,
+    ) -> Result<List<CommitActivity>> {
+        let path = stats_api_path(self, "commit_activity");
+        Ok(List::from_response(client.get_stats(&path).await?))
+    }
+
+    /// Gets the lines added and deleted each week.
+    ///
+    /// Retries like [`contributor_activity`].
+    ///
+    /// [`contributor_activity`]: #method.contributor_activity
+    #[cfg(feature = "blocking")]
+    pub fn code_frequency(&self) -> Result<List<CodeFrequency>> {
+        self.code_frequency_with(&Client::new()?)
+    }
+
+    /// Like [`code_frequency`], but uses a configured [`Client`].
+    ///
+    /// [`code_frequency`]: #method.code_frequency
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn code_frequency_with(&self, client: &Client) -> Result<List<CodeFrequency>> {
+        let path = stats_api_path(self, "code_frequency");
+        Ok(List::from_response(client.get_stats(&path)?))
+    }
+
+    /// Like [`code_frequency`], but uses an [`AsyncClient`].
+    ///
+    /// [`code_frequency`]: #method.code_frequency
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn code_frequency_async(&self, client: &AsyncClient) -> Result<List<CodeFrequency>> {
+        let path = stats_api_path(self, "code_frequency");
+        Ok(List::from_response(client.get_stats(&path).await?))
+    }
+
+    /// Gets the weekly commits of the last year, by everyone and by the
+    /// owner.
+    ///
+    /// Retries like [`contributor_activity`].
+    ///
+    /// [`contributor_activity`]: #method.contributor_activity
+    #[cfg(feature = "blocking")]
+    pub fn participation(&self) -> Result<Participation> {
+        self.participation_with(&Client::new()?)
+    }
+
+    /// Like [`participation`], but uses a configured [`Client`].
+    ///
+    /// [`participation`]: #method.participation
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn participation_with(&self, client: &Client) -> Result<Participation> {
+        let response = client.get_stats(&stats_api_path(self, "participation"))?;
+        Ok(Participation {
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Like [`participation`], but uses an [`AsyncClient`].
+    ///
+    /// [`participation`]: #method.participation
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn participation_async(&self, client: &AsyncClient) -> Result<Participation> {
+        let path = stats_api_path(self, "participation");
+        let response = client.get_stats(&path).await?;
+        Ok(Participation {
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Gets the commits in each hour of the week.
+    ///
+    /// Retries like [`contributor_activity`].
+    ///
+    /// [`contributor_activity`]: #method.contributor_activity
+    #[cfg(feature = "blocking")]
+    pub fn punch_card(&self) -> Result<List<PunchCardHour>> {
+        self.punch_card_with(&Client::new()?)
+    }
+
+    /// Like [`punch_card`], but uses a configured [`Client`].
+    ///
+    /// [`punch_card`]: #method.punch_card
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn punch_card_with(&self, client: &Client) -> Result<List<PunchCardHour>> {
+        let path = stats_api_path(self, "punch_card");
+        Ok(List::from_response(client.get_stats(&path)?))
+    }
+
+    /// Like [`punch_card`], but uses an [`AsyncClient`].
+    ///
+    /// [`punch_card`]: #method.punch_card
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn punch_card_async(&self, client: &AsyncClient) -> Result<List<PunchCardHour>> {
+        let path = stats_api_path(self, "punch_card");
+        Ok(List::from_response(client.get_stats(&path).await?))
+    }
+}
+
+impl ContributorActivity {
+    /// `None` if the contributor's commits are not linked to a [Github]
+    /// user.
+    ///
+    /// [Github]: https://github.com/
+    pub fn author(&self) -> Option<&User> {
+        self.author.as_ref()
+    }
+
+    /// Number of commits.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Oldest week first.
+    pub fn weeks(&self) -> &[ContributorWeek] {
+        &self.weeks
+    }
+
+    /// Lines added in every week.
+    pub fn additions(&self) -> u64 {
+        self.weeks.iter().map(ContributorWeek::additions).sum()
+    }
+
+    /// Lines deleted in every week.
+    pub fn deletions(&self) -> u64 {
+        self.weeks.iter().map(ContributorWeek::deletions).sum()
+    }
+}
+
+impl ContributorWeek {
+    /// The start of the week.
+    pub fn week(&self) -> &DateTime<Utc> {
+        &self.week
+    }
+
+    pub fn additions(&self) -> u64 {
+        self.additions
+    }
+
+    pub fn deletions(&self) -> u64 {
+        self.deletions
+    }
+
+    pub fn commits(&self) -> u64 {
+        self.commits
+    }
+}
+
+impl CommitActivity {
+    /// Commits on each day, starting on Sunday.
+    pub fn days(&self) -> &[u64] {
+        &self.days
+    }
+
+    /// Commits in the week.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The start of the week.
+    pub fn week(&self) -> &DateTime<Utc> {
+        &self.week
+    }
+}
+
+impl CodeFrequency {
+    /// The start of the week.
+    pub fn week(&self) -> &DateTime<Utc> {
+        &self.0
+    }
+
+    pub fn additions(&self) -> u64 {
+        self.1.unsigned_abs()
+    }
+
+    /// [Github] reports deletions as negative numbers, but this is always
+    /// positive.
+    ///
+    /// [Github]: https://github.com/
+    pub fn deletions(&self) -> u64 {
+        self.2.unsigned_abs()
+    }
+}
+
+impl Participation {
+    /// Commits by everyone, oldest week first.
+    pub fn all(&self) -> &[u64] {
+        &self.all
+    }
+
+    /// Commits by the owner, oldest week first.
+    pub fn owner(&self) -> &[u64] {
+        &self.owner
+    }
+
+    /// Commits by everyone but the owner, oldest week first.
+    pub fn others(&self) -> Vec<u64> {
+        self.all
+            .iter()
+            .zip(self.owner.iter().chain(std::iter::repeat(&0)))
+            .map(|(all, owner)| all.saturating_sub(*owner))
+            .collect()
+    }
+
+    /// The rate limit after getting these statistics.
+    ///
+    /// `None` if the response did not report it.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
+}
+
+impl PunchCardHour {
+    /// From `0` for Sunday to `6` for Saturday.
+    pub fn day(&self) -> u8 {
+        self.0
+    }
+
+    /// From `0` to `23`.
+    pub fn hour(&self) -> u8 {
+        self.1
+    }
+
+    pub fn commits(&self) -> u64 {
+        self.2
+    }
+}
+
+fn stats_api_path(repo: &Repo, stats: &str) -> String {
+    format!("/repos/{}/stats/{}", repo.full_name(), stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contributor_activity() {
+        let activity: Vec<ContributorActivity> = serde_json::from_str(&format!(
+            r#"[{{
+                "author": {},
+                "total": 135,
+                "weeks": [
+                    {{"w": 1367712000, "a": 6898, "d": 77, "c": 10}},
+                    {{"w": 1368316800, "a": 2, "d": 3, "c": 1}}
+                ]
+            }}]"#,
+            include_str!("../../tests/fixtures/users/octocat.json")
+        ))
+        .unwrap();
+        let activity = &activity[0];
+
+        assert_eq!("octocat", activity.author().unwrap().login());
+        assert_eq!(135, activity.total());
+        assert_eq!(1367712000, activity.weeks()[0].week().timestamp());
+        assert_eq!(10, activity.weeks()[0].commits());
+        assert_eq!(6900, activity.additions());
+        assert_eq!(80, activity.deletions());
+    }
+
+    #[test]
+    fn other_stats() {
+        let commits: Vec<CommitActivity> = serde_json::from_str(
+            r#"[{"days": [0, 3, 26, 20, 39, 1, 0], "total": 89, "week": 1336280400}]"#,
+        )
+        .unwrap();
+        assert_eq!(26, commits[0].days()[2]);
+        assert_eq!(89, commits[0].total());
+
+        let frequency: Vec<CodeFrequency> =
+            serde_json::from_str("[[1302998400, 1124, -435]]").unwrap();
+        assert_eq!(1302998400, frequency[0].week().timestamp());
+        assert_eq!(1124, frequency[0].additions());
+        assert_eq!(435, frequency[0].deletions());
+
+        let participation: Participation =
+            serde_json::from_str(r#"{"all": [11, 21, 15], "owner": [3, 2, 15]}"#).unwrap();
+        assert_eq!(vec![8, 19, 0], participation.others());
+
+        let punch_card: Vec<PunchCardHour> = serde_json::from_str("[[0, 2, 32]]").unwrap();
+        assert_eq!(
+            (0, 2, 32),
+            (
+                punch_card[0].day(),
+                punch_card[0].hour(),
+                punch_card[0].commits()
+            )
+        );
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+    use crate::Error;
+
+    fn client(server: &MockServer, max_wait: Duration) -> Client {
+        server
+            .builder()
+            .stats_backoff(Duration::from_millis(10))
+            .stats_max_wait(max_wait)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn retries_until_computed() {
+        let server = MockServer::start(vec![
+            MockResponse::json(202, "{}"),
+            MockResponse::json(202, "{}"),
+            MockResponse::json(202, "{}"),
+            MockResponse::json(200, "[[0, 2, 32], [1, 14, 7]]").rate_limit(5000, 4996),
+        ]);
+        let client = client(&server, Duration::from_secs(10));
+
+        let punch_card = hello_world().punch_card_with(&client).unwrap();
+        assert_eq!(7, punch_card.items()[1].commits());
+        assert_eq!(4996, punch_card.rate_limit().unwrap().remaining());
+
+        assert_eq!(
+            vec!["GET /repos/octocat/Hello-World/stats/punch_card"; 4],
+            server.request_lines()
+        );
+    }
+
+    #[test]
+    fn fails_after_max_wait() {
+        let server = MockServer::start(vec![
+            MockResponse::json(202, "{}"),
+            MockResponse::json(202, "{}"),
+            MockResponse::json(202, "{}"),
+        ]);
+        // Waits 10ms, then the remaining 5ms.
+        let client = client(&server, Duration::from_millis(15));
+
+        match hello_world().participation_with(&client) {
+            Err(Error::NotReady { url }) => assert!(url.ends_with("/stats/participation")),
+            other => panic!("expected NotReady, got {:?}", other),
+        }
+        assert_eq!(3, server.request_lines().len());
+    }
+
+    #[test]
+    fn no_content_is_empty() {
+        let server = MockServer::start(vec![
+            MockResponse::json(204, ""),
+            MockResponse::json(204, "").rate_limit(60, 59),
+        ]);
+        let client = client(&server, Duration::from_secs(10));
+
+        assert!(hello_world()
+            .code_frequency_with(&client)
+            .unwrap()
+            .is_empty());
+        let participation = hello_world().participation_with(&client).unwrap();
+        assert!(participation.all().is_empty());
+        assert_eq!(59, participation.rate_limit().unwrap().remaining());
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod async_request_tests {
+    use std::time::Duration;
+
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+
+    #[tokio::test]
+    async fn retries_until_computed() {
+        let repo = hello_world();
+        let server = MockServer::start(vec![
+            MockResponse::json(202, "{}"),
+            MockResponse::json(202, "{}"),
+            MockResponse::json(
+                200,
+                r#"[{"days": [0, 3, 26, 20, 39, 1, 0], "total": 89, "week": 1336280400}]"#,
+            ),
+        ]);
+        let client = server
+            .builder()
+            .stats_backoff(Duration::from_millis(10))
+            .build_async()
+            .unwrap();
+
+        let activity = repo.commit_activity_async(&client).await.unwrap();
+        assert_eq!(89, activity.items()[0].total());
+        assert_eq!(
+            vec!["GET /repos/octocat/Hello-World/stats/commit_activity"; 3],
+            server.request_lines()
+        );
+    }
+}
@@ -0,0 +1,547 @@
+use chrono::prelude::{DateTime, Utc};
+use serde::Deserialize;
+
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{Error, List, RateLimit, Repo, Result};
+
+/// How traffic is grouped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Period {
+    Day,
+    Week,
+}
+
+/// Views or clones of a repository over the last 14 days.
+///
+/// [Github] only keeps 14 days of traffic, so a longer history has to be
+/// built from snapshots taken at least every two weeks, with [`merge`].
+///
+/// ```no_run
+/// use github_stats::{Period, Repo, Traffic};
+///
+/// # #[cfg(feature = "blocking")]
+/// # fn run(repo: &Repo, history: &mut Traffic) -> github_stats::Result<()> {
+/// history.merge(&repo.views(Period::Day)?)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [Github]: https://github.com/
+/// [`merge`]: #method.merge
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Traffic {
+    count: u64,
+    uniques: u64,
+    #[serde(alias = "views", alias = "clones")]
+    counts: Vec<TrafficCount>,
+    // Not in the response, so set after getting it.
+    #[serde(skip)]
+    period: Option<Period>,
+    #[serde(skip)]
+    rate_limit: Option<RateLimit>,
+}
+
+/// The views or clones in one day or week.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TrafficCount {
+    timestamp: DateTime<Utc>,
+    count: u64,
+    uniques: u64,
+}
+
+/// A site that linked to a repository in the last 14 days.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Referrer {
+    referrer: String,
+    count: u64,
+    uniques: u64,
+}
+
+/// One of a repository's most viewed pages in the last 14 days.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PopularPath {
+    path: String,
+    title: String,
+    count: u64,
+    uniques: u64,
+}
+
+impl Repo {
+    /// Gets the views of this repository over the last 14 days.
+    ///
+    /// Requires push access to the repository.
+    #[cfg(feature = "blocking")]
+    pub fn views(&self, per: Period) -> Result<Traffic> {
+        self.views_with(&Client::new()?, per)
+    }
+
+    /// Like [`views`], but uses a configured [`Client`].
+    ///
+    /// [`views`]: #method.views
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn views_with(&self, client: &Client, per: Period) -> Result<Traffic> {
+        let response = client.get(&traffic_api_path(self, "views", per))?;
+        Ok(Traffic {
+            period: Some(per),
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Like [`views`], but uses an [`AsyncClient`].
+    ///
+    /// [`views`]: #method.views
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn views_async(&self, client: &AsyncClient, per: Period) -> Result<Traffic> {
+        let response = client.get(&traffic_api_path(self, "views", per)).await?;
+        Ok(Traffic {
+            period: Some(per),
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Gets the clones of this repository over the last 14 days.
+    ///
+    /// Requires push access to the repository.
+    #[cfg(feature = "blocking")]
+    pub fn clones(&self, per: Period) -> Result<Traffic> {
+        self.clones_with(&Client::new()?, per)
+    }
+
+    /// Like [`clones`], but uses a configured [`Client`].
+    ///
+    /// [`clones`]: #method.clones
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn clones_with(&self, client: &Client, per: Period) -> Result<Traffic> {
+        let response = client.get(&traffic_api_path(self, "clones", per))?;
+        Ok(Traffic {
+            period: Some(per),
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Like [`clones`], but uses an [`AsyncClient`].
+    ///
+    /// [`clones`]: #method.clones
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn clones_async(&self, client: &AsyncClient, per: Period) -> Result<Traffic> {
+        let response = client.get(&traffic_api_path(self, "clones", per)).await?;
+        Ok(Traffic {
+            period: Some(per),
+            rate_limit: response.rate_limit,
+            ..response.value
+        })
+    }
+
+    /// Gets the top 10 sites that linked to this repository over the last 14
+    /// days.
+    ///
+    /// Requires push access to the repository.
+    #[cfg(feature = "blocking")]
+    pub fn referrers(&self) -> Result<List<Referrer>> {
+        self.referrers_with(&Client::new()?)
+    }
+
+    /// Like [`referrers`], but uses a configured [`Client`].
+    ///
+    /// [`referrers`]: #method.referrers
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn referrers_with(&self, client: &Client) -> Result<List<Referrer>> {
+        let response = client.get(&popular_api_path(self, "referrers"))?;
+        Ok(List::from_response(response))
+    }
+
+    /// Like [`referrers`], but uses an [`AsyncClient`].
+    ///
+    /// [`referrers`]: #method.referrers
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn referrers_async(&self, client: &AsyncClient) -> Result<List<Referrer>> {
+        let path = popular_api_path(self, "referrers");
+        Ok(List::from_response(client.get(&path).await?))
+    }
+
+    /// Gets the top 10 most viewed pages of this repository over the last 14
+    /// days.
+    ///
+    /// Requires push access to the repository.
+    #[cfg(feature = "blocking")]
+    pub fn popular_paths(&self) -> Result<List<PopularPath>> {
+        self.popular_paths_with(&Client::new()?)
+    }
+
+    /// Like [`popular_paths`], but uses a configured [`Client`].
+    ///
+    /// [`popular_paths`]: #method.popular_paths
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn popular_paths_with(&self, client: &Client) -> Result<List<PopularPath>> {
+        let response = client.get(&popular_api_path(self, "paths"))?;
+        Ok(List::from_response(response))
+    }
+
+    /// Like [`popular_paths`], but uses an [`AsyncClient`].
+    ///
+    /// [`popular_paths`]: #method.popular_paths
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn popular_paths_async(&self, client: &AsyncClient) -> Result<List<PopularPath>> {
+        let path = popular_api_path(self, "paths");
+        Ok(List::from_response(client.get(&path).await?))
+    }
+}
+
+impl Period {
+    /// The value as it is used in the URL, like `"day"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Period::Day => "day",
+            Period::Week => "week",
+        }
+    }
+}
+
+impl Traffic {
+    /// *Adds* the days or weeks of `other` that this traffic does not have
+    /// yet.
+    ///
+    /// When both have the same day or week, the larger numbers are kept,
+    /// because the latest day or week of a snapshot may not be over yet.
+    ///
+    /// Unique visitors can't be added up across days or weeks, so the total
+    /// [`uniques`] is the larger of the two totals, and may be less than the
+    /// true number of unique visitors.
+    ///
+    /// The rate limit is not kept, because the result is not a response.
+    ///
+    /// Fails with [`Error::PeriodMismatch`], without changing this traffic,
+    /// if both have a [`period`] and they are not the same.
+    ///
+    /// [`uniques`]: #method.uniques
+    /// [`period`]: #method.period
+    /// [`Error::PeriodMismatch`]: enum.Error.html#variant.PeriodMismatch
+    pub fn merge(&mut self, other: &Traffic) -> Result<()> {
+        if let (Some(expected), Some(found)) = (self.period, other.period) {
+            if expected != found {
+                return Err(Error::PeriodMismatch { expected, found });
+            }
+        }
+        self.period = self.period.or(other.period);
+
+        // Also sorts this traffic's own counts, in case they were not.
+        let counts = std::mem::take(&mut self.counts);
+        for count in counts.iter().chain(&other.counts) {
+            match self
+                .counts
+                .binary_search_by(|c| c.timestamp.cmp(&count.timestamp))
+            {
+                Ok(i) => {
+                    let existing = &mut self.counts[i];
+                    existing.count = existing.count.max(count.count);
+                    existing.uniques = existing.uniques.max(count.uniques);
+                }
+                Err(i) => self.counts.insert(i, count.clone()),
+            }
+        }
+        self.count = self.counts.iter().map(TrafficCount::count).sum();
+        self.uniques = self.uniques.max(other.uniques);
+        self.rate_limit = None;
+        Ok(())
+    }
+
+    /// Whether this is traffic per day or per week.
+    ///
+    /// `None` if this traffic was not gotten from [Github], like an empty
+    /// history that nothing was merged into yet.
+    ///
+    /// [Github]: https://github.com/
+    pub fn period(&self) -> Option<Period> {
+        self.period
+    }
+
+    /// Views or clones in every day or week.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Unique visitors or cloners in every day or week.
+    pub fn uniques(&self) -> u64 {
+        self.uniques
+    }
+
+    /// Oldest first.
+    pub fn counts(&self) -> &[TrafficCount] {
+        &self.counts
+    }
+
+    /// The rate limit after getting this traffic.
+    ///
+    /// `None` if the response did not report it, or if other traffic was
+    /// [`merge`]d into this traffic.
+    ///
+    /// [`merge`]: #method.merge
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
+}
+
+impl TrafficCount {
+    /// The start of the day or week.
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn uniques(&self) -> u64 {
+        self.uniques
+    }
+}
+
+impl Referrer {
+    /// The referring site, like `"google.com"`.
+    pub fn referrer(&self) -> &str {
+        &self.referrer
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn uniques(&self) -> u64 {
+        self.uniques
+    }
+}
+
+impl PopularPath {
+    /// Like `"/github/hubot"`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The page's title.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn uniques(&self) -> u64 {
+        self.uniques
+    }
+}
+
+fn traffic_api_path(repo: &Repo, traffic: &str, per: Period) -> String {
+    format!(
+        "/repos/{}/traffic/{}?per={}",
+        repo.full_name(),
+        traffic,
+        per.as_str()
+    )
+}
+
+fn popular_api_path(repo: &Repo, popular: &str) -> String {
+    format!("/repos/{}/traffic/popular/{}", repo.full_name(), popular)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Datelike;
+
+    use super::*;
+
+    fn traffic(counts: &[(&str, u64, u64)], uniques: u64) -> Traffic {
+        let counts: Vec<String> = counts
+            .iter()
+            .map(|(day, count, uniques)| {
+                format!(
+                    r#"{{"timestamp": "{}T00:00:00Z", "count": {}, "uniques": {}}}"#,
+                    day, count, uniques
+                )
+            })
+            .collect();
+        let json = format!(
+            r#"{{"count": 0, "uniques": {}, "views": [{}]}}"#,
+            uniques,
+            counts.join(", ")
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn views_and_clones() {
+        let views: Traffic = serde_json::from_str(
+            r#"{
+                "count": 14850,
+                "uniques": 3782,
+                "views": [
+                    {"timestamp": "2016-10-10T00:00:00Z", "count": 440, "uniques": 143},
+                    {"timestamp": "2016-10-11T00:00:00Z", "count": 1308, "uniques": 414}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(14850, views.count());
+        assert_eq!(414, views.counts()[1].uniques());
+
+        let clones: Traffic = serde_json::from_str(
+            r#"{
+                "count": 173,
+                "uniques": 128,
+                "clones": [{"timestamp": "2016-10-10T00:00:00Z", "count": 2, "uniques": 1}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(2, clones.counts()[0].count());
+    }
+
+    #[test]
+    fn popular() {
+        let referrers: Vec<Referrer> =
+            serde_json::from_str(r#"[{"referrer": "Google", "count": 4, "uniques": 3}]"#).unwrap();
+        assert_eq!("Google", referrers[0].referrer());
+
+        let paths: Vec<PopularPath> = serde_json::from_str(
+            r#"[{
+                "path": "/github/hubot",
+                "title": "github/hubot: A customizable life embetterment robot.",
+                "count": 3542,
+                "uniques": 2225
+            }]"#,
+        )
+        .unwrap();
+        assert_eq!("/github/hubot", paths[0].path());
+        assert_eq!(2225, paths[0].uniques());
+    }
+
+    #[test]
+    fn merge_snapshots() {
+        let mut history = traffic(&[("2020-01-01", 10, 5), ("2020-01-02", 4, 2)], 6);
+        let snapshot = traffic(
+            &[
+                ("2020-01-02", 7, 3),
+                ("2020-01-03", 1, 1),
+                ("2019-12-31", 2, 2),
+            ],
+            4,
+        );
+
+        history.merge(&snapshot).unwrap();
+        history.merge(&snapshot).unwrap();
+
+        let counts: Vec<(String, u64, u64)> = history
+            .counts()
+            .iter()
+            .map(|c| {
+                (
+                    c.timestamp().date_naive().to_string(),
+                    c.count(),
+                    c.uniques(),
+                )
+            })
+            .collect();
+        let expected = vec![
+            (String::from("2019-12-31"), 2, 2),
+            (String::from("2020-01-01"), 10, 5),
+            (String::from("2020-01-02"), 7, 3),
+            (String::from("2020-01-03"), 1, 1),
+        ];
+        assert_eq!(expected, counts);
+        assert_eq!(20, history.count());
+        assert_eq!(6, history.uniques());
+    }
+
+    #[test]
+    fn merge_unsorted() {
+        let mut history = traffic(
+            &[
+                ("2020-01-02", 4, 2),
+                ("2020-01-01", 10, 5),
+                ("2020-01-02", 6, 1),
+            ],
+            6,
+        );
+
+        history.merge(&Traffic::default()).unwrap();
+
+        let days: Vec<(u32, u64)> = history
+            .counts()
+            .iter()
+            .map(|c| (c.timestamp().day(), c.count()))
+            .collect();
+        assert_eq!(vec![(1, 10), (2, 6)], days);
+        assert_eq!(16, history.count());
+    }
+
+    #[test]
+    fn merge_periods() {
+        let mut history = Traffic::default();
+        let daily = Traffic {
+            period: Some(Period::Day),
+            ..traffic(&[], 0)
+        };
+        history.merge(&daily).unwrap();
+        assert_eq!(Some(Period::Day), history.period());
+
+        let weekly = Traffic {
+            period: Some(Period::Week),
+            ..traffic(&[("2020-01-06", 7, 3)], 3)
+        };
+        match history.merge(&weekly) {
+            Err(Error::PeriodMismatch { expected, found }) => {
+                assert_eq!((Period::Day, Period::Week), (expected, found));
+            }
+            other => panic!("expected PeriodMismatch, got {:?}", other),
+        }
+        assert!(history.counts().is_empty());
+        assert!(history.merge(&traffic(&[], 0)).is_ok());
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::repository::hello_world;
+
+    #[test]
+    fn requests_traffic() {
+        let repo = hello_world();
+        let server = MockServer::start(vec![
+            MockResponse::json(200, r#"{"count": 0, "uniques": 0, "clones": []}"#)
+                .rate_limit(5000, 4999),
+            MockResponse::json(200, "[]").rate_limit(5000, 4998),
+        ]);
+        let client = server.client();
+
+        let clones = repo.clones_with(&client, Period::Week).unwrap();
+        assert_eq!(0, clones.count());
+        assert_eq!(Some(Period::Week), clones.period());
+        assert_eq!(4999, clones.rate_limit().unwrap().remaining());
+        let referrers = repo.referrers_with(&client).unwrap();
+        assert!(referrers.is_empty());
+        assert_eq!(4998, referrers.rate_limit().unwrap().remaining());
+
+        assert_eq!(
+            vec![
+                "GET /repos/octocat/Hello-World/traffic/clones?per=week",
+                "GET /repos/octocat/Hello-World/traffic/popular/referrers",
+            ],
+            server.request_lines()
+        );
+    }
+}
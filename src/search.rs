@@ -1,81 +1,207 @@
 use std::fmt;
-use std::error::Error;
+use std::marker::PhantomData;
 
 use serde::Deserialize;
-use serde_json::Value;
 
-use crate::Result;
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+use crate::client::DEFAULT_BASE_URL;
+use crate::pagination::Page;
+#[cfg(feature = "async")]
+use crate::pagination::{self, PaginatedStream};
+#[cfg(feature = "blocking")]
+use crate::pagination::Paginator;
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{RateLimit, Repo, Result, User};
 
-pub use query::Query;
+pub use items::{
+    CodeResult, CommitDetails, CommitResult, GitUser, Issue, IssuePullRequest, Label, MinimalRepo,
+    SearchItem, Topic,
+};
+pub use qualifiers::{Fork, InField, Is, State, Type};
+pub use query::{Login, Query, MAX_OPERATORS};
+pub use range::{Range, SearchDate};
+pub use sort::{
+    CodeSort, CommitSort, IssueSort, LabelSort, Order, RepoSort, SortKey, TopicSort, UserSort,
+};
 
+mod items;
+mod qualifiers;
 mod query;
+mod range;
+mod sort;
 
 /// Uses [Github]'s search API.
 ///
 /// # Example
 /// ## Get merged PRs
 ///
-/// ```
+/// ```no_run
+/// # #[cfg(feature = "blocking")] {
+/// use github_stats::search::Is;
 /// use github_stats::{Query, Search};
 ///
 /// let query = Query::new()
 ///     .repo("rust-lang", "rust")
-///     .is("pr")
-///     .is("merged");
+///     .is(Is::Pr)
+///     .is(Is::Merged);
 ///
-/// let results = Search::new("issues", &query)
+/// let results = Search::issues(&query)
 ///     .per_page(10)
 ///     .page(1)
 ///     .search();
 ///
 /// match results {
-///     Ok(results) => { /* do stuff */ }
+///     Ok(results) => {
+///         for pr in results.items() {
+///             println!("#{} {}", pr.number(), pr.title());
+///         }
+///     }
 ///     Err(e) => eprintln!(":("),
 /// }
+/// # }
 /// ```
 ///
 /// [Github]: https://github.com/
-pub struct Search {
-    search_area: String,
-    query: String,
+pub struct Search<T: SearchItem> {
+    query: Query,
     per_page: usize,
     page: usize,
+    repository_id: Option<u64>,
+    sort: Option<T::Sort>,
+    order: Option<Order>,
+    item: PhantomData<fn() -> T>,
 }
 
+/// The search API only returns the first 1000 results of a search.
+pub const MAX_RESULTS: usize = 1000;
+
+/// One page of search results.
 #[derive(Debug, Deserialize)]
-pub struct SearchResults {
+pub struct SearchResults<T> {
     total_count: u64,
-    items: Vec<Value>,
+    incomplete_results: bool,
+    items: Vec<T>,
+    #[serde(skip)]
+    rate_limit: Option<RateLimit>,
+    #[serde(skip)]
+    next: Option<String>,
 }
 
-#[derive(Debug)]
-pub struct SearchError(String);
+impl Search<Issue> {
+    /// Searches issues and pull requests.
+    pub fn issues(query: &Query) -> Self {
+        Search::new(query)
+    }
+}
 
-impl fmt::Display for SearchError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,"{}",self.0)
+impl Search<Repo> {
+    /// Searches repositories.
+    ///
+    /// Repositories found with search do not have a
+    /// [`subscribers_count`](../struct.Repo.html#method.subscribers_count).
+    pub fn repositories(query: &Query) -> Self {
+        Search::new(query)
+    }
+}
+
+impl Search<User> {
+    /// Searches users and organizations.
+    pub fn users(query: &Query) -> Self {
+        Search::new(query)
+    }
+}
+
+impl Search<CodeResult> {
+    /// Searches the contents of files.
+    ///
+    /// [Github] requires code searches to be authenticated.
+    ///
+    /// [Github]: https://github.com/
+    pub fn code(query: &Query) -> Self {
+        Search::new(query)
+    }
+}
+
+impl Search<CommitResult> {
+    /// Searches commits on repositories' default branches.
+    pub fn commits(query: &Query) -> Self {
+        Search::new(query)
+    }
+}
+
+impl Search<Topic> {
+    /// Searches topics.
+    pub fn topics(query: &Query) -> Self {
+        Search::new(query)
     }
 }
 
-impl Error for SearchError {}
+impl Search<Label> {
+    /// Searches the labels of a single repository.
+    ///
+    /// `repository_id` is the repository's [`id`](../struct.Repo.html#method.id).
+    pub fn labels(repository_id: u64, query: &Query) -> Self {
+        Search::new(query).repository_id(repository_id)
+    }
+}
 
-impl Search {
+impl<T: SearchItem> Search<T> {
     /// Creates a new search configuration.
     ///
-    /// # Available Choices for `area`
-    /// - `"issues"`
-    /// *More choices will be made available as this project continues.*
-    /// *Other choices, such as `"users"`, are technically possible, but*
-    /// *are not yet properly supported.*
-    pub fn new(area: &str, query: &Query) -> Self {
+    /// The kind of item being searched for decides which part of the search
+    /// API is used. Constructors like [`issues`] can be used instead to avoid
+    /// naming the kind of item.
+    ///
+    /// ```
+    /// use github_stats::search::{Is, Issue, Query, Search};
+    ///
+    /// let search = Search::<Issue>::new(&Query::new().is(Is::Pr));
+    /// ```
+    ///
+    /// [`issues`]: #method.issues
+    pub fn new(query: &Query) -> Self {
         Search {
-            search_area: String::from(area),
-            query: query.to_string(),
+            query: query.clone(),
             per_page: 10,
             page: 1,
+            repository_id: None,
+            sort: None,
+            order: None,
+            item: PhantomData,
         }
     }
 
+    /// The repository to search in. Only needed for label searches.
+    pub fn repository_id(mut self, repository_id: u64) -> Self {
+        self.repository_id = Some(repository_id);
+        self
+    }
+
+    /// How to sort the results. Defaults to best match.
+    ///
+    /// ```
+    /// use github_stats::search::{Is, IssueSort, Order, Query, Search};
+    ///
+    /// let search = Search::issues(&Query::new().is(Is::Pr))
+    ///     .sort(IssueSort::Created)
+    ///     .order(Order::Asc);
+    /// ```
+    pub fn sort(mut self, sort: T::Sort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Defaults to [`Desc`]. Ignored unless [`sort`] is set.
+    ///
+    /// [`Desc`]: enum.Order.html#variant.Desc
+    /// [`sort`]: #method.sort
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = Some(order);
+        self
+    }
+
     /// Defaults to 10.
     pub fn per_page(mut self, per_page: usize) -> Self {
         self.per_page = per_page;
@@ -90,26 +216,137 @@ impl Search {
 
     /// Moves one page forward.
     pub fn next_page(&mut self) {
-        if self.page < std::usize::MAX {
+        if self.page < usize::MAX {
             self.page += 1;
         }
     }
 
     /// Moves one page backward.
     pub fn prev_page(&mut self) {
-        if self.page > std::usize::MIN {
+        if self.page > usize::MIN {
             self.page -= 1;
         }
     }
 
     /// Runs the search.
-    pub fn search(&self) -> Result<SearchResults> {
-        let results: SearchResults = reqwest::get(&self.to_string())?.json()?;
+    #[cfg(feature = "blocking")]
+    pub fn search(&self) -> Result<SearchResults<T>> {
+        self.search_with(&Client::new()?)
+    }
+
+    /// Runs the search using a configured [`Client`].
+    ///
+    /// [`Client`]: ../struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn search_with(&self, client: &Client) -> Result<SearchResults<T>> {
+        let response = client.get(&self.checked_api_path()?)?;
+        let results = SearchResults {
+            rate_limit: response.rate_limit,
+            next: response.next,
+            ..response.value
+        };
+
+        Ok(results)
+    }
+
+    /// Lazily gets every item matching the query, starting at the current
+    /// page.
+    ///
+    /// Stops at the end of the results, or after the search API's limit of
+    /// [`MAX_RESULTS`].
+    ///
+    /// [`MAX_RESULTS`]: constant.MAX_RESULTS.html
+    #[cfg(feature = "blocking")]
+    pub fn items(&self) -> Result<Paginator<T>> {
+        Ok(self.items_with(&Client::new()?))
+    }
+
+    /// Like [`items`], but uses a configured [`Client`].
+    ///
+    /// [`items`]: #method.items
+    /// [`Client`]: ../struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn items_with(&self, client: &Client) -> Paginator<T> {
+        let limit = Some(self.remaining_results());
+        match self.checked_api_path() {
+            Ok(path) => Paginator::new::<SearchResults<T>>(client, &path, limit),
+            Err(e) => Paginator::failed::<SearchResults<T>>(client, e),
+        }
+    }
+
+    /// Runs the search using an [`AsyncClient`].
+    ///
+    /// [`AsyncClient`]: ../struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn search_async(&self, client: &AsyncClient) -> Result<SearchResults<T>> {
+        let response = client.get(&self.checked_api_path()?).await?;
+        let results = SearchResults {
+            rate_limit: response.rate_limit,
+            next: response.next,
+            ..response.value
+        };
+
         Ok(results)
     }
+
+    /// Like [`items`], but uses an [`AsyncClient`] and returns a stream.
+    ///
+    /// [`items`]: #method.items
+    /// [`AsyncClient`]: ../struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub fn items_async(&self, client: &AsyncClient) -> PaginatedStream<T>
+    where
+        T: Send + 'static,
+    {
+        let limit = Some(self.remaining_results());
+        match self.checked_api_path() {
+            Ok(path) => pagination::stream::<SearchResults<T>>(client, &path, limit),
+            Err(e) => pagination::failed_stream(e),
+        }
+    }
+
+    // How many results can be reached from the current page.
+    fn remaining_results(&self) -> usize {
+        let skipped = self.page.saturating_sub(1).saturating_mul(self.per_page);
+        MAX_RESULTS.saturating_sub(skipped)
+    }
+
+    // Like `api_path`, but fails if Github would reject the query.
+    fn checked_api_path(&self) -> Result<String> {
+        self.query.check(T::AREA)?;
+        Ok(self.api_path())
+    }
+
+    // The API path and query string, relative to the base URL.
+    fn api_path(&self) -> String {
+        let repository_id = match self.repository_id {
+            Some(id) => format!("&repository_id={}", id),
+            None => String::new(),
+        };
+        let sort = match self.sort {
+            Some(sort) => format!("&sort={}", sort.as_str()),
+            None => String::new(),
+        };
+        let order = match self.order {
+            Some(order) => format!("&order={}", order),
+            None => String::new(),
+        };
+        let query = self.query.to_string();
+        let query: String = form_urlencoded::byte_serialize(query.as_bytes()).collect();
+        format!(
+            "/search/{0}?per_page={1}&page={2}{3}{4}{5}&q={6}",
+            T::AREA,
+            self.per_page,
+            self.page,
+            repository_id,
+            sort,
+            order,
+            query,
+        )
+    }
 }
 
-impl SearchResults {
+impl<T> SearchResults<T> {
     /// Gets total count of values matching query.
     ///
     /// This ignores `per_page`. If you only want the total count, it is
@@ -118,18 +355,297 @@ impl SearchResults {
         self.total_count
     }
 
+    /// `true` if the search timed out before finding every match.
+    pub fn incomplete_results(&self) -> bool {
+        self.incomplete_results
+    }
+
     /// Items matching the query.
-    pub fn items(&self) -> &Vec<Value> {
+    pub fn items(&self) -> &[T] {
         &self.items
     }
+
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// `false` if this is the last page of results.
+    pub fn has_next_page(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// The search API's rate limit after running the search.
+    ///
+    /// The search API's limit is separate from, and much lower than, the
+    /// limit for the rest of the API.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
 }
 
-impl fmt::Display for Search {
+impl<T: SearchItem> Page for SearchResults<T> {
+    type Item = T;
+
+    fn into_items(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T: SearchItem> fmt::Display for Search<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "https://api.github.com/search/{0}?per_page={1}&page={2}&q={3}",
-            self.search_area, self.per_page, self.page, self.query,
+        write!(f, "{}{}", DEFAULT_BASE_URL, self.api_path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issues() {
+        let results: SearchResults<Issue> =
+            serde_json::from_str(include_str!("../tests/fixtures/search/issues.json")).unwrap();
+        let items = results.items();
+
+        assert_eq!(2, results.total_count());
+        assert!(items[0].is_pull_request());
+        assert_eq!(State::Closed, items[0].state());
+        assert!(items[0].pull_request().unwrap().merged_at().is_some());
+        assert_eq!(Some(false), items[0].draft());
+        assert_eq!("A-diagnostics", items[0].labels()[0].name());
+        assert!(!items[1].is_pull_request());
+        assert_eq!(State::Open, items[1].state());
+        assert_eq!(None, items[1].body());
+        assert_eq!(None, items[1].closed_at());
+    }
+
+    #[test]
+    fn repositories() {
+        let results: SearchResults<Repo> =
+            serde_json::from_str(include_str!("../tests/fixtures/search/repositories.json"))
+                .unwrap();
+
+        assert_eq!("rust-lang/rust", results.items()[0].full_name());
+        assert_eq!(None, results.items()[0].subscribers_count());
+    }
+
+    #[test]
+    fn users() {
+        let results: SearchResults<User> =
+            serde_json::from_str(include_str!("../tests/fixtures/search/users.json")).unwrap();
+
+        assert_eq!("Organization", results.items()[0].r#type());
+    }
+
+    #[test]
+    fn code() {
+        let results: SearchResults<CodeResult> =
+            serde_json::from_str(include_str!("../tests/fixtures/search/code.json")).unwrap();
+        let item = &results.items()[0];
+
+        assert_eq!("src/search.rs", item.path());
+        assert_eq!("spenserblack/github-stats-rs", item.repository().full_name());
+    }
+
+    #[test]
+    fn commits() {
+        let results: SearchResults<CommitResult> =
+            serde_json::from_str(include_str!("../tests/fixtures/search/commits.json")).unwrap();
+        let item = &results.items()[0];
+
+        assert_eq!("Spenser Black", item.commit().author().name());
+        assert!(item.committer().is_none());
+        assert_eq!("spenserblack", item.author().unwrap().login());
+    }
+
+    #[test]
+    fn topics() {
+        let results: SearchResults<Topic> =
+            serde_json::from_str(include_str!("../tests/fixtures/search/topics.json")).unwrap();
+        let item = &results.items()[0];
+
+        assert_eq!("rust", item.name());
+        assert!(item.featured());
+        assert_eq!(None, results.items()[1].display_name());
+    }
+
+    #[test]
+    fn labels() {
+        let results: SearchResults<Label> =
+            serde_json::from_str(include_str!("../tests/fixtures/search/labels.json")).unwrap();
+
+        assert_eq!("bug", results.items()[0].name());
+        assert!(results.items()[0].default());
+    }
+
+    #[test]
+    fn label_search_path() {
+        let search = Search::labels(724712, &Query::new().r#in("bug", InField::Name));
+
+        let url = "https://api.github.com/search/labels?per_page=10&page=1&repository_id=724712&";
+        assert!(search.to_string().starts_with(url));
+    }
+
+    #[test]
+    fn sorted_search_path() {
+        let query = Query::new().is(Is::Pr);
+
+        let search = Search::issues(&query).sort(IssueSort::Updated);
+        let url = "https://api.github.com/search/issues?per_page=10&page=1&sort=updated&q=";
+        assert!(search.to_string().starts_with(url));
+
+        let search = Search::issues(&query).order(Order::Asc).sort(IssueSort::Created);
+        let url = "https://api.github.com/search/issues?per_page=10&page=1&sort=created&order=asc&";
+        assert!(search.to_string().starts_with(url));
+
+        let search = Search::repositories(&query).sort(RepoSort::HelpWantedIssues);
+        assert!(search.to_string().contains("&sort=help-wanted-issues&"));
+
+        let search = Search::commits(&query).sort(CommitSort::AuthorDate).order(Order::Desc);
+        assert!(search.to_string().contains("&sort=author-date&order=desc&"));
+    }
+
+    // The `q` parameter of a search's URL.
+    fn encoded_query(search: &Search<Issue>) -> String {
+        let url = search.to_string();
+        let (_, q) = url.split_once("&q=").unwrap();
+        String::from(q)
+    }
+
+    #[test]
+    fn encoded_query_round_trip() {
+        let queries = vec![
+            (
+                Query::new().r#in("[BUG]", InField::Name),
+                "%5BBUG%5D+in%3Aname",
+            ),
+            (
+                Query::new().label("good first issue"),
+                "label%3A%22good+first+issue%22",
+            ),
+            (
+                Query::new().r#in(r#"say "hi""#, InField::Body),
+                "%22say+%5C%22hi%5C%22%22+in%3Abody",
+            ),
+            (
+                Query::new().language("C#").r#in("R&D", InField::Title),
+                "R%26D+in%3Atitle+language%3AC%23",
+            ),
+            (
+                Query::new().language("c++").label("100%"),
+                "label%3A100%25+language%3Ac%2B%2B",
+            ),
+            (
+                Query::new().label("日本語"),
+                "label%3A%E6%97%A5%E6%9C%AC%E8%AA%9E",
+            ),
+        ];
+
+        for (query, expected) in queries {
+            let search = Search::issues(&query);
+            let encoded = encoded_query(&search);
+            assert_eq!(expected, encoded);
+
+            let pair = format!("q={}", encoded);
+            let (_, decoded) = form_urlencoded::parse(pair.as_bytes()).next().unwrap();
+            assert_eq!(query.to_string(), decoded);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod request_tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::Error;
+
+    #[derive(Deserialize)]
+    struct Id {
+        id: u64,
+    }
+
+    impl SearchItem for Id {
+        const AREA: &'static str = "issues";
+        type Sort = IssueSort;
+    }
+
+    fn page(ids: &[u64]) -> String {
+        let items: Vec<String> = ids.iter().map(|id| format!(r#"{{"id": {}}}"#, id)).collect();
+        format!(
+            r#"{{"total_count": 3, "incomplete_results": false, "items": [{}]}}"#,
+            items.join(", "),
         )
     }
+
+    #[test]
+    fn items_follow_next_link() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, &page(&[1, 2]))
+                .header("Link", r#"<{url}/search/issues?page=2>; rel="next""#),
+            MockResponse::json(200, &page(&[3])),
+        ]);
+        let client = server.client();
+        let search = Search::<Id>::new(&Query::new().is(Is::Pr)).per_page(2);
+
+        let ids: Vec<u64> = search
+            .items_with(&client)
+            .map(|item| item.unwrap().id)
+            .collect();
+
+        assert_eq!(vec![1, 2, 3], ids);
+        let lines = server.request_lines();
+        assert!(lines[0].starts_with("GET /search/issues?per_page=2&page=1&"));
+        assert_eq!("GET /search/issues?page=2", lines[1]);
+    }
+
+    #[test]
+    fn items_stop_at_max_results() {
+        let server = MockServer::start(vec![MockResponse::json(200, &page(&[1, 2]))
+            .header("Link", r#"<{url}/search/issues?page=1000>; rel="next""#)]);
+        let client = server.client();
+        let search = Search::<Id>::new(&Query::new().is(Is::Pr))
+            .per_page(1)
+            .page(MAX_RESULTS);
+
+        assert_eq!(1, search.items_with(&client).count());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn too_many_operators() {
+        let server = MockServer::start(Vec::new());
+        let client = server.client();
+        let authors = ["a", "b", "c", "d", "e", "f", "g"];
+        let query = Query::new().any(authors.iter().map(|a| Query::new().author(*a)));
+        let search = Search::<Id>::new(&query);
+
+        assert!(matches!(search.search_with(&client), Err(Error::InvalidQuery(_))));
+        let items: Vec<Result<Id>> = search.items_with(&client).collect();
+        assert_eq!(1, items.len());
+        assert!(matches!(items[0], Err(Error::InvalidQuery(_))));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn invalid_for_area() {
+        let server = MockServer::start(Vec::new());
+        let client = server.client();
+        let search = Search::repositories(&Query::new().is(Is::Merged));
+
+        assert!(matches!(search.search_with(&client), Err(Error::InvalidQuery(_))));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn last_page() {
+        let server = MockServer::start(vec![MockResponse::json(200, &page(&[1]))]);
+        let client = server.client();
+
+        let results = Search::<Id>::new(&Query::new())
+            .search_with(&client)
+            .unwrap();
+
+        assert!(!results.has_next_page());
+        assert!(!results.incomplete_results());
+    }
 }
@@ -0,0 +1,520 @@
+//! The kinds of items that can be searched for.
+
+use chrono::prelude::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use super::qualifiers::State;
+use super::sort::{
+    CodeSort, CommitSort, IssueSort, LabelSort, RepoSort, SortKey, TopicSort, UserSort,
+};
+use crate::{Repo, User};
+
+/// A kind of item that can be searched for.
+///
+/// Each kind of item is searched for in its own area of the search API.
+pub trait SearchItem: DeserializeOwned {
+    /// The area of the search API, like `"issues"` in `/search/issues`.
+    const AREA: &'static str;
+
+    /// The ways results can be sorted, like [`IssueSort`].
+    ///
+    /// [`IssueSort`]: enum.IssueSort.html
+    type Sort: SortKey;
+}
+
+/// An issue or pull request found with the search API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Issue {
+    id: u64,
+    node_id: String,
+    number: u64,
+    title: String,
+    state: State,
+    locked: bool,
+    user: User,
+    labels: Vec<Label>,
+    assignees: Vec<User>,
+    comments: u64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    author_association: String,
+    body: Option<String>,
+    html_url: String,
+    url: String,
+    pull_request: Option<IssuePullRequest>,
+    draft: Option<bool>,
+}
+
+/// Links from an [`Issue`] to the pull request it represents.
+///
+/// [`Issue`]: struct.Issue.html
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssuePullRequest {
+    url: String,
+    html_url: String,
+    diff_url: String,
+    patch_url: String,
+    merged_at: Option<DateTime<Utc>>,
+}
+
+/// A label on an issue or pull request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Label {
+    id: u64,
+    node_id: String,
+    url: String,
+    name: String,
+    color: String,
+    default: bool,
+    description: Option<String>,
+}
+
+/// A file found with the code search API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodeResult {
+    name: String,
+    path: String,
+    sha: String,
+    url: String,
+    git_url: String,
+    html_url: String,
+    repository: MinimalRepo,
+}
+
+/// A commit found with the commit search API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitResult {
+    sha: String,
+    node_id: String,
+    url: String,
+    html_url: String,
+    commit: CommitDetails,
+    author: Option<User>,
+    committer: Option<User>,
+    repository: MinimalRepo,
+}
+
+/// The git data of a [`CommitResult`] or a [`PullRequestCommit`].
+///
+/// [`CommitResult`]: struct.CommitResult.html
+/// [`PullRequestCommit`]: ../struct.PullRequestCommit.html
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitDetails {
+    message: String,
+    author: GitUser,
+    committer: GitUser,
+    comment_count: u64,
+}
+
+/// The author or committer recorded in a git commit.
+///
+/// This is not necessarily a [Github] user.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GitUser {
+    name: String,
+    email: String,
+    date: DateTime<Utc>,
+}
+
+/// A topic found with the topic search API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Topic {
+    name: String,
+    display_name: Option<String>,
+    short_description: Option<String>,
+    description: Option<String>,
+    created_by: Option<String>,
+    released: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    featured: bool,
+    curated: bool,
+}
+
+/// The few details of a repository that are included with code and commit
+/// search results.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MinimalRepo {
+    id: u64,
+    node_id: String,
+    name: String,
+    full_name: String,
+    owner: User,
+    private: bool,
+    html_url: String,
+    description: Option<String>,
+    fork: bool,
+    url: String,
+}
+
+impl SearchItem for Issue {
+    const AREA: &'static str = "issues";
+    type Sort = IssueSort;
+}
+
+impl SearchItem for Repo {
+    const AREA: &'static str = "repositories";
+    type Sort = RepoSort;
+}
+
+impl SearchItem for User {
+    const AREA: &'static str = "users";
+    type Sort = UserSort;
+}
+
+impl SearchItem for CodeResult {
+    const AREA: &'static str = "code";
+    type Sort = CodeSort;
+}
+
+impl SearchItem for CommitResult {
+    const AREA: &'static str = "commits";
+    type Sort = CommitSort;
+}
+
+impl SearchItem for Topic {
+    const AREA: &'static str = "topics";
+    type Sort = TopicSort;
+}
+
+impl SearchItem for Label {
+    const AREA: &'static str = "labels";
+    type Sort = LabelSort;
+}
+
+impl Issue {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Merged pull requests are `Closed`.
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Who opened the issue.
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn assignees(&self) -> &[User] {
+        &self.assignees
+    }
+
+    /// Number of comments.
+    pub fn comments(&self) -> u64 {
+        self.comments
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn closed_at(&self) -> Option<&DateTime<Utc>> {
+        self.closed_at.as_ref()
+    }
+
+    /// Like `"OWNER"`, `"CONTRIBUTOR"` or `"NONE"`.
+    pub fn author_association(&self) -> &str {
+        &self.author_association
+    }
+
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// `Some` if this "issue" is actually a pull request.
+    pub fn pull_request(&self) -> Option<&IssuePullRequest> {
+        self.pull_request.as_ref()
+    }
+
+    pub fn is_pull_request(&self) -> bool {
+        self.pull_request.is_some()
+    }
+
+    /// `None` if this is not a pull request.
+    pub fn draft(&self) -> Option<bool> {
+        self.draft
+    }
+}
+
+impl IssuePullRequest {
+    /// API URL for the pull request.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn diff_url(&self) -> &str {
+        &self.diff_url
+    }
+
+    pub fn patch_url(&self) -> &str {
+        &self.patch_url
+    }
+
+    /// `None` if the pull request has not been merged.
+    pub fn merged_at(&self) -> Option<&DateTime<Utc>> {
+        self.merged_at.as_ref()
+    }
+}
+
+impl Label {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Hex color without the leading `#`, like `"d73a4a"`.
+    pub fn color(&self) -> &str {
+        &self.color
+    }
+
+    /// `true` if this is one of the labels every repository starts with.
+    pub fn default(&self) -> bool {
+        self.default
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+impl CodeResult {
+    /// The file's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The file's path in the repository.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The file's blob SHA.
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn git_url(&self) -> &str {
+        &self.git_url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn repository(&self) -> &MinimalRepo {
+        &self.repository
+    }
+}
+
+impl CommitResult {
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn commit(&self) -> &CommitDetails {
+        &self.commit
+    }
+
+    /// `None` if the author's email is not linked to a [Github] user.
+    ///
+    /// [Github]: https://github.com/
+    pub fn author(&self) -> Option<&User> {
+        self.author.as_ref()
+    }
+
+    /// `None` if the committer's email is not linked to a [Github] user.
+    ///
+    /// [Github]: https://github.com/
+    pub fn committer(&self) -> Option<&User> {
+        self.committer.as_ref()
+    }
+
+    pub fn repository(&self) -> &MinimalRepo {
+        &self.repository
+    }
+}
+
+impl CommitDetails {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn author(&self) -> &GitUser {
+        &self.author
+    }
+
+    pub fn committer(&self) -> &GitUser {
+        &self.committer
+    }
+
+    pub fn comment_count(&self) -> u64 {
+        self.comment_count
+    }
+}
+
+impl GitUser {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn date(&self) -> &DateTime<Utc> {
+        &self.date
+    }
+}
+
+impl Topic {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    pub fn short_description(&self) -> Option<&str> {
+        self.short_description.as_deref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn created_by(&self) -> Option<&str> {
+        self.created_by.as_deref()
+    }
+
+    pub fn released(&self) -> Option<&str> {
+        self.released.as_deref()
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn featured(&self) -> bool {
+        self.featured
+    }
+
+    pub fn curated(&self) -> bool {
+        self.curated
+    }
+}
+
+impl MinimalRepo {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    pub fn owner(&self) -> &User {
+        &self.owner
+    }
+
+    pub fn private(&self) -> bool {
+        self.private
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn fork(&self) -> bool {
+        self.fork
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
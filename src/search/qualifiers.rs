@@ -0,0 +1,349 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+// Search areas, matching `SearchItem::AREA`.
+const ISSUES: &str = "issues";
+const REPOSITORIES: &str = "repositories";
+const USERS: &str = "users";
+const CODE: &str = "code";
+const COMMITS: &str = "commits";
+const TOPICS: &str = "topics";
+const LABELS: &str = "labels";
+
+/// Values for `is:` qualifiers.
+///
+/// Each value can only be used when searching some kinds of items, and
+/// [`Search`] fails with [`InvalidQuery`] if it is used for another.
+///
+/// [`Search`]: struct.Search.html
+/// [`InvalidQuery`]: ../enum.Error.html#variant.InvalidQuery
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Is {
+    /// Open issues and pull requests.
+    Open,
+    /// Closed issues and pull requests.
+    Closed,
+    /// Merged pull requests.
+    Merged,
+    /// Pull requests that were closed without being merged.
+    Unmerged,
+    /// Draft pull requests.
+    Draft,
+    /// Issues and pull requests with locked conversations.
+    Locked,
+    Unlocked,
+    /// Pull requests.
+    Pr,
+    /// Issues.
+    Issue,
+    /// Public issues, pull requests, repositories and commits.
+    Public,
+    /// Private issues, pull requests, repositories and commits.
+    Private,
+    /// Users and repositories that can be sponsored.
+    Sponsorable,
+    /// Curated topics.
+    Curated,
+    /// Featured topics.
+    Featured,
+}
+
+/// Values for `type:` qualifiers.
+///
+/// [`Pr`] and [`Issue`] can only be used when searching issues, and [`User`]
+/// and [`Org`] can only be used when searching users.
+///
+/// [`Pr`]: #variant.Pr
+/// [`Issue`]: #variant.Issue
+/// [`User`]: #variant.User
+/// [`Org`]: #variant.Org
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Type {
+    Pr,
+    Issue,
+    User,
+    Org,
+}
+
+/// Values for `state:` qualifiers, which can only be used when searching
+/// issues.
+///
+/// Also the state of an [`Issue`], a [`PullRequest`] or a [`Milestone`].
+///
+/// [`Issue`]: struct.Issue.html
+/// [`PullRequest`]: ../struct.PullRequest.html
+/// [`Milestone`]: ../struct.Milestone.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum State {
+    Open,
+    Closed,
+}
+
+/// Values for `fork:` qualifiers, which can only be used when searching
+/// repositories.
+///
+/// Forks are left out of repository searches unless one of these is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Fork {
+    /// Includes forks with other repositories.
+    Include,
+    /// Only searches forks.
+    Only,
+}
+
+/// Fields for `in:` qualifiers, which limit where keywords are searched for.
+///
+/// Each field can only be used when searching some kinds of items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InField {
+    /// Titles of issues and pull requests.
+    Title,
+    /// Bodies of issues and pull requests.
+    Body,
+    /// Comments on issues and pull requests.
+    Comments,
+    /// Names of repositories and labels, or full names of users.
+    Name,
+    /// Descriptions of repositories and labels.
+    Description,
+    /// READMEs of repositories.
+    Readme,
+    /// Topics of repositories.
+    Topics,
+    /// Logins of users.
+    Login,
+    /// Public emails of users.
+    Email,
+    /// Contents of files.
+    File,
+    /// Paths of files.
+    Path,
+}
+
+impl Is {
+    const ALL: [Is; 14] = [
+        Is::Open,
+        Is::Closed,
+        Is::Merged,
+        Is::Unmerged,
+        Is::Draft,
+        Is::Locked,
+        Is::Unlocked,
+        Is::Pr,
+        Is::Issue,
+        Is::Public,
+        Is::Private,
+        Is::Sponsorable,
+        Is::Curated,
+        Is::Featured,
+    ];
+
+    /// The value as it is used in a query, like `"merged"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Is::Open => "open",
+            Is::Closed => "closed",
+            Is::Merged => "merged",
+            Is::Unmerged => "unmerged",
+            Is::Draft => "draft",
+            Is::Locked => "locked",
+            Is::Unlocked => "unlocked",
+            Is::Pr => "pr",
+            Is::Issue => "issue",
+            Is::Public => "public",
+            Is::Private => "private",
+            Is::Sponsorable => "sponsorable",
+            Is::Curated => "curated",
+            Is::Featured => "featured",
+        }
+    }
+
+    // The value with the name `name`, like `"merged"`.
+    pub(crate) fn from_name(name: &str) -> Option<Is> {
+        Is::ALL.iter().copied().find(|v| v.as_str() == name)
+    }
+
+    fn areas(self) -> &'static [&'static str] {
+        match self {
+            Is::Open
+            | Is::Closed
+            | Is::Merged
+            | Is::Unmerged
+            | Is::Draft
+            | Is::Locked
+            | Is::Unlocked
+            | Is::Pr
+            | Is::Issue => &[ISSUES],
+            Is::Public | Is::Private => &[ISSUES, REPOSITORIES, COMMITS],
+            Is::Sponsorable => &[USERS, REPOSITORIES],
+            Is::Curated | Is::Featured => &[TOPICS],
+        }
+    }
+}
+
+impl Type {
+    const ALL: [Type; 4] = [Type::Pr, Type::Issue, Type::User, Type::Org];
+
+    /// The value as it is used in a query, like `"pr"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Type::Pr => "pr",
+            Type::Issue => "issue",
+            Type::User => "user",
+            Type::Org => "org",
+        }
+    }
+
+    // The value with the name `name`, like `"pr"`.
+    pub(crate) fn from_name(name: &str) -> Option<Type> {
+        Type::ALL.iter().copied().find(|v| v.as_str() == name)
+    }
+
+    fn areas(self) -> &'static [&'static str] {
+        match self {
+            Type::Pr | Type::Issue => &[ISSUES],
+            Type::User | Type::Org => &[USERS],
+        }
+    }
+}
+
+impl State {
+    const ALL: [State; 2] = [State::Open, State::Closed];
+
+    /// The value as it is used in a query, like `"open"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            State::Open => "open",
+            State::Closed => "closed",
+        }
+    }
+
+    // The value with the name `name`, like `"open"`.
+    pub(crate) fn from_name(name: &str) -> Option<State> {
+        State::ALL.iter().copied().find(|v| v.as_str() == name)
+    }
+
+    fn areas(self) -> &'static [&'static str] {
+        &[ISSUES]
+    }
+}
+
+impl Fork {
+    const ALL: [Fork; 2] = [Fork::Include, Fork::Only];
+
+    /// The value as it is used in a query, like `"only"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Fork::Include => "true",
+            Fork::Only => "only",
+        }
+    }
+
+    // The value with the name `name`, like `"only"`.
+    pub(crate) fn from_name(name: &str) -> Option<Fork> {
+        Fork::ALL.iter().copied().find(|v| v.as_str() == name)
+    }
+
+    fn areas(self) -> &'static [&'static str] {
+        &[REPOSITORIES]
+    }
+}
+
+impl InField {
+    const ALL: [InField; 11] = [
+        InField::Title,
+        InField::Body,
+        InField::Comments,
+        InField::Name,
+        InField::Description,
+        InField::Readme,
+        InField::Topics,
+        InField::Login,
+        InField::Email,
+        InField::File,
+        InField::Path,
+    ];
+
+    /// The value as it is used in a query, like `"title"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InField::Title => "title",
+            InField::Body => "body",
+            InField::Comments => "comments",
+            InField::Name => "name",
+            InField::Description => "description",
+            InField::Readme => "readme",
+            InField::Topics => "topics",
+            InField::Login => "login",
+            InField::Email => "email",
+            InField::File => "file",
+            InField::Path => "path",
+        }
+    }
+
+    // The value with the name `name`, like `"title"`.
+    pub(crate) fn from_name(name: &str) -> Option<InField> {
+        InField::ALL.iter().copied().find(|v| v.as_str() == name)
+    }
+
+    fn areas(self) -> &'static [&'static str] {
+        match self {
+            InField::Title | InField::Body | InField::Comments => &[ISSUES],
+            InField::Name => &[REPOSITORIES, USERS, LABELS],
+            InField::Description => &[REPOSITORIES, LABELS],
+            InField::Readme | InField::Topics => &[REPOSITORIES],
+            InField::Login | InField::Email => &[USERS],
+            InField::File | InField::Path => &[CODE],
+        }
+    }
+}
+
+// The areas a qualifier's value can be used in, or `None` if the value is not
+// one of the typed values above, so that it is not checked.
+pub(crate) fn areas(qualifier: &str, value: &str) -> Option<&'static [&'static str]> {
+    match qualifier {
+        "is" => Is::from_name(value).map(Is::areas),
+        "type" => Type::from_name(value).map(Type::areas),
+        "state" => State::from_name(value).map(State::areas),
+        "in" => InField::from_name(value).map(InField::areas),
+        "fork" => Fork::from_name(value).map(Fork::areas),
+        _ => None,
+    }
+}
+
+impl fmt::Display for Is {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Fork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for InField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
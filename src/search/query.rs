@@ -1,17 +1,149 @@
+use std::borrow::Cow;
 use std::fmt;
+use std::str::FromStr;
 
-use crate::Repo;
+use super::qualifiers::{self, Fork, InField, Is, State, Type};
+use super::range::{Range, SearchDate};
+use crate::{Error, Repo, Result, User};
 
-#[derive(Default)]
+mod parse;
+
+/// [Github] rejects queries with more than this many `AND`, `OR` and `NOT`
+/// operators.
+///
+/// [Github]: https://github.com/
+pub const MAX_OPERATORS: usize = 5;
+
+/// A search query, using [Github]'s search syntax.
+///
+/// Displays as the query would be typed into [Github]'s search bar, with
+/// values that have spaces or quotes put in quotes. [`Search`] takes care of
+/// encoding it for the URL.
+///
+/// ```
+/// use github_stats::search::State;
+/// use github_stats::Query;
+///
+/// let query = Query::new()
+///     .repo("rust-lang", "rust")
+///     .label("good first issue")
+///     .state(State::Open);
+///
+/// assert_eq!(
+///     r#"repo:rust-lang/rust label:"good first issue" state:open"#,
+///     query.to_string(),
+/// );
+/// ```
+///
+/// Qualifiers can be excluded with [`not`], and alternatives can be given with
+/// [`any`].
+///
+/// ```
+/// use github_stats::search::Is;
+/// use github_stats::Query;
+///
+/// let query = Query::new()
+///     .is(Is::Issue)
+///     .any_label(&["bug", "regression"])
+///     .not(Query::new().label("wontfix"))
+///     .any(vec![
+///         Query::new().author("octocat"),
+///         Query::new().assignee("octocat"),
+///     ]);
+///
+/// assert_eq!(
+///     "is:issue label:bug,regression -label:wontfix (author:octocat OR assignee:octocat)",
+///     query.to_string(),
+/// );
+/// ```
+///
+/// A query can also be parsed from [Github]'s search syntax, like a search
+/// copied from [Github]'s search bar.
+///
+/// ```
+/// use github_stats::search::Is;
+/// use github_stats::Query;
+///
+/// let query: Query = "is:pr -label:wontfix (author:octocat OR assignee:octocat)"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(query, query.to_string().parse().unwrap());
+/// ```
+///
+/// [Github]: https://github.com/
+/// [`Search`]: struct.Search.html
+/// [`not`]: #method.not
+/// [`any`]: #method.any
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Query {
-    repo: Vec<String>,
-    is: Vec<String>,
-    r#in: Vec<In>,
-    label: Vec<String>,
-    r#type: Vec<String>,
-    state: Vec<String>,
-    no: Vec<String>,
-    language: Vec<String>,
+    // Kept in the order of their keys, so that qualifiers of the same kind are
+    // displayed together. Keywords go first, and groups go last.
+    terms: Vec<Term>,
+}
+
+/// A [Github] user or organization, given either by login or as a [`User`].
+///
+/// [Github]: https://github.com/
+/// [`User`]: ../struct.User.html
+pub trait Login {
+    fn login(&self) -> &str;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Term {
+    Keyword(String),
+    // Matches any of the values.
+    Qualifier(Key, Vec<String>),
+    In(In),
+    // Matches any of the queries.
+    Any(Vec<Query>),
+    Not(Box<Term>),
+}
+
+// The kinds of qualifiers, in the order they are displayed.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Key {
+    Repo,
+    Org,
+    User,
+    Is,
+    In,
+    Label,
+    Type,
+    State,
+    No,
+    Language,
+    Topic,
+    License,
+    Archived,
+    Mirror,
+    Template,
+    Fork,
+    Created,
+    Updated,
+    Closed,
+    Merged,
+    Pushed,
+    Comments,
+    Reactions,
+    Interactions,
+    Stars,
+    Forks,
+    Size,
+    Topics,
+    GoodFirstIssues,
+    HelpWantedIssues,
+    Author,
+    Assignee,
+    Mentions,
+    Commenter,
+    Involves,
+    ReviewedBy,
+    ReviewRequested,
+    TeamReviewRequested,
+    Team,
+    Raw(String),
 }
 
 impl Query {
@@ -21,44 +153,77 @@ impl Query {
         }
     }
 
+    /// *Adds* a keyword or phrase to search for.
+    ///
+    /// Results in `keyword`, or in `"some keywords"` if there is more than
+    /// one word.
+    pub fn keyword(self, keyword: &str) -> Self {
+        self.push(Term::Keyword(String::from(keyword)))
+    }
+
     pub fn from_repo(repo: Repo) -> Self {
-        let repo = vec![String::from(repo.full_name())];
-        Query {
-            repo,
-            ..Default::default()
-        }
+        Query::new().qualifier(Key::Repo, repo.full_name())
     }
 
     /// *Adds* a repo to the query.
     ///
     /// Results in `repo:user/repo`.
-    pub fn repo(mut self, user: &str, repo: &str) -> Self {
-        self.repo.push(format!("{}/{}", user, repo));
-        self
+    pub fn repo(self, user: &str, repo: &str) -> Self {
+        self.qualifier(Key::Repo, &format!("{}/{}", user, repo))
+    }
+
+    /// *Adds* an `org` statement to the query, for repositories, issues and
+    /// other items owned by an organization.
+    ///
+    /// Results in `org:login`. `org` can be a login or a [`User`].
+    ///
+    /// ```
+    /// use github_stats::search::{RepoSort, Search};
+    /// use github_stats::Query;
+    ///
+    /// // The most starred repositories of the organization.
+    /// let search = Search::repositories(&Query::new().org("rust-lang")).sort(RepoSort::Stars);
+    /// ```
+    ///
+    /// [`User`]: ../struct.User.html
+    pub fn org(self, org: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::Org, org.login())
+    }
+
+    /// *Adds* a `user` statement to the query, for items owned by a user.
+    ///
+    /// Results in `user:login`.
+    pub fn user(self, user: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::User, user.login())
     }
 
     /// *Adds* an `is` statement to the query.
     ///
-    /// Results in `is:statement`.
-    pub fn is(mut self, statement: &str) -> Self {
-        self.is.push(String::from(statement));
-        self
+    /// Results in `is:statement`, like `is:merged`.
+    pub fn is(self, statement: Is) -> Self {
+        self.qualifier(Key::Is, statement.as_str())
     }
 
     /// *Adds* an `in` statement to the query
     ///
-    /// Results in `keyword in:field`.
-    pub fn r#in(mut self, keyword: &str, field: &str) -> Self {
-        self.r#in.push(In(String::from(keyword), String::from(field)));
-        self
+    /// Results in `keyword in:field`, like `[BUG] in:title`.
+    pub fn r#in(self, keyword: &str, field: InField) -> Self {
+        self.push(Term::In(In(String::from(keyword), String::from(field.as_str()))))
     }
 
     /// *Adds* a `label` statement to the query.
     ///
     /// Results in `label:statement`.
-    pub fn label(mut self, statement: &str) -> Self {
-        self.label.push(String::from(statement));
-        self
+    pub fn label(self, statement: &str) -> Self {
+        self.qualifier(Key::Label, statement)
+    }
+
+    /// *Adds* a `label` statement to the query that matches any of `labels`.
+    ///
+    /// Results in `label:first,second`.
+    pub fn any_label(self, labels: &[&str]) -> Self {
+        let labels = labels.iter().map(|label| String::from(*label)).collect();
+        self.push(Term::Qualifier(Key::Label, labels))
     }
 
     /// *Adds* a `type` statement to the query.
@@ -66,91 +231,682 @@ impl Query {
     /// Results in `type:statement`.
     ///
     /// *Use `r#type` to escape `type` keyword.
-    pub fn r#type(mut self, statement: &str) -> Self {
-        self.r#type.push(String::from(statement));
-        self
+    pub fn r#type(self, statement: Type) -> Self {
+        self.qualifier(Key::Type, statement.as_str())
+    }
+
+    /// *Adds* a `state` statement to the query.
+    ///
+    /// Results in `state:statement`, like `state:open` or `state:closed`.
+    pub fn state(self, statement: State) -> Self {
+        self.qualifier(Key::State, statement.as_str())
     }
 
     /// *Adds* a `no` statement to the query.
     ///
     /// Results in `no:statement`.
-    pub fn no(mut self, statement: &str) -> Self {
-        self.no.push(String::from(statement));
-        self
+    pub fn no(self, statement: &str) -> Self {
+        self.qualifier(Key::No, statement)
     }
 
     /// *Adds* a `language` statement to the query.
     ///
     /// Results in `language:statement`.
-    pub fn language(mut self, statement: &str) -> Self {
-        self.language.push(String::from(statement));
+    pub fn language(self, statement: &str) -> Self {
+        self.qualifier(Key::Language, statement)
+    }
+
+    /// *Adds* a `topic` statement to the query, for repositories with the
+    /// topic.
+    ///
+    /// Results in `topic:statement`.
+    pub fn topic(self, statement: &str) -> Self {
+        self.qualifier(Key::Topic, statement)
+    }
+
+    /// *Adds* a `license` statement to the query, for repositories with the
+    /// license.
+    ///
+    /// Results in `license:keyword`, where `keyword` is a license's
+    /// [`key`](../struct.License.html#method.key), like `mit`.
+    pub fn license(self, keyword: &str) -> Self {
+        self.qualifier(Key::License, keyword)
+    }
+
+    /// *Adds* an `archived` statement to the query, for archived or
+    /// unarchived repositories.
+    ///
+    /// Results in `archived:true` or `archived:false`.
+    pub fn archived(self, archived: bool) -> Self {
+        self.qualifier(Key::Archived, &archived.to_string())
+    }
+
+    /// *Adds* a `mirror` statement to the query, for repositories that are
+    /// or aren't mirrors.
+    ///
+    /// Results in `mirror:true` or `mirror:false`.
+    pub fn mirror(self, mirror: bool) -> Self {
+        self.qualifier(Key::Mirror, &mirror.to_string())
+    }
+
+    /// *Adds* a `template` statement to the query, for repositories that are
+    /// or aren't templates.
+    ///
+    /// Results in `template:true` or `template:false`.
+    pub fn template(self, template: bool) -> Self {
+        self.qualifier(Key::Template, &template.to_string())
+    }
+
+    /// *Adds* a `fork` statement to the query, to include forks in a
+    /// repository search.
+    ///
+    /// Results in `fork:true` or `fork:only`.
+    pub fn fork(self, fork: Fork) -> Self {
+        self.qualifier(Key::Fork, fork.as_str())
+    }
+
+    /// *Adds* a `created` range to the query, for when issues, pull requests
+    /// or repositories were created.
+    ///
+    /// Results in `created:range`, like `created:>=2020-01-31`. The range can
+    /// use dates or dates and times, and can be made with Rust's range syntax.
+    /// See [`Range`](enum.Range.html).
+    pub fn created<D: SearchDate>(self, range: impl Into<Range<D>>) -> Self {
+        self.date_range(Key::Created, range.into())
+    }
+
+    /// *Adds* an `updated` range to the query, for when issues or pull
+    /// requests were last updated.
+    ///
+    /// Results in `updated:range`.
+    pub fn updated<D: SearchDate>(self, range: impl Into<Range<D>>) -> Self {
+        self.date_range(Key::Updated, range.into())
+    }
+
+    /// *Adds* a `closed` range to the query, for when issues or pull requests
+    /// were closed.
+    ///
+    /// Results in `closed:range`.
+    pub fn closed<D: SearchDate>(self, range: impl Into<Range<D>>) -> Self {
+        self.date_range(Key::Closed, range.into())
+    }
+
+    /// *Adds* a `merged` range to the query, for when pull requests were
+    /// merged.
+    ///
+    /// Results in `merged:range`.
+    pub fn merged<D: SearchDate>(self, range: impl Into<Range<D>>) -> Self {
+        self.date_range(Key::Merged, range.into())
+    }
+
+    /// *Adds* a `pushed` range to the query, for when repositories were last
+    /// pushed to.
+    ///
+    /// Results in `pushed:range`.
+    pub fn pushed<D: SearchDate>(self, range: impl Into<Range<D>>) -> Self {
+        self.date_range(Key::Pushed, range.into())
+    }
+
+    /// *Adds* a `comments` range to the query, for the number of comments on
+    /// issues and pull requests.
+    ///
+    /// Results in `comments:range`, like `comments:>10`.
+    pub fn comments(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::Comments, &range.into().to_string())
+    }
+
+    /// *Adds* a `reactions` range to the query, for the number of reactions
+    /// on issues and pull requests.
+    ///
+    /// Results in `reactions:range`.
+    pub fn reactions(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::Reactions, &range.into().to_string())
+    }
+
+    /// *Adds* an `interactions` range to the query, for the number of
+    /// reactions and comments on issues and pull requests.
+    ///
+    /// Results in `interactions:range`.
+    pub fn interactions(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::Interactions, &range.into().to_string())
+    }
+
+    /// *Adds* a `stars` range to the query, for the number of stars on
+    /// repositories.
+    ///
+    /// Results in `stars:range`.
+    pub fn stars(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::Stars, &range.into().to_string())
+    }
+
+    /// *Adds* a `forks` range to the query, for the number of forks of
+    /// repositories.
+    ///
+    /// Results in `forks:range`.
+    pub fn forks(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::Forks, &range.into().to_string())
+    }
+
+    /// *Adds* a `size` range to the query, for the size of repositories in
+    /// kilobytes.
+    ///
+    /// Results in `size:range`.
+    pub fn size(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::Size, &range.into().to_string())
+    }
+
+    /// *Adds* a `topics` range to the query, for the number of topics
+    /// repositories have.
+    ///
+    /// Results in `topics:range`.
+    pub fn topics(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::Topics, &range.into().to_string())
+    }
+
+    /// *Adds* a `good-first-issues` range to the query, for the number of
+    /// issues labeled `good first issue` on repositories.
+    ///
+    /// Results in `good-first-issues:range`.
+    pub fn good_first_issues(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::GoodFirstIssues, &range.into().to_string())
+    }
+
+    /// *Adds* a `help-wanted-issues` range to the query, for the number of
+    /// issues labeled `help wanted` on repositories.
+    ///
+    /// Results in `help-wanted-issues:range`.
+    pub fn help_wanted_issues(self, range: impl Into<Range<u64>>) -> Self {
+        self.qualifier(Key::HelpWantedIssues, &range.into().to_string())
+    }
+
+    /// *Adds* an `author` statement to the query, for issues, pull requests
+    /// and commits created by `user`.
+    ///
+    /// Results in `author:user`. `user` can be a login or a [`User`].
+    ///
+    /// ```
+    /// use github_stats::search::Is;
+    /// use github_stats::Query;
+    ///
+    /// let query = Query::new().author("octocat").is(Is::Pr);
+    /// ```
+    ///
+    /// [`User`]: ../struct.User.html
+    pub fn author(self, user: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::Author, user.login())
+    }
+
+    /// *Adds* an `assignee` statement to the query.
+    ///
+    /// Results in `assignee:user`.
+    pub fn assignee(self, user: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::Assignee, user.login())
+    }
+
+    /// *Adds* a `mentions` statement to the query.
+    ///
+    /// Results in `mentions:user`.
+    pub fn mentions(self, user: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::Mentions, user.login())
+    }
+
+    /// *Adds* a `commenter` statement to the query.
+    ///
+    /// Results in `commenter:user`.
+    pub fn commenter(self, user: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::Commenter, user.login())
+    }
+
+    /// *Adds* an `involves` statement to the query, for issues and pull
+    /// requests that `user` created, is assigned to, is mentioned in or
+    /// commented on.
+    ///
+    /// Results in `involves:user`.
+    pub fn involves(self, user: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::Involves, user.login())
+    }
+
+    /// *Adds* a `reviewed-by` statement to the query.
+    ///
+    /// Results in `reviewed-by:user`.
+    pub fn reviewed_by(self, user: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::ReviewedBy, user.login())
+    }
+
+    /// *Adds* a `review-requested` statement to the query.
+    ///
+    /// Results in `review-requested:user`.
+    pub fn review_requested(self, user: &(impl Login + ?Sized)) -> Self {
+        self.qualifier(Key::ReviewRequested, user.login())
+    }
+
+    /// *Adds* a `team-review-requested` statement to the query.
+    ///
+    /// Results in `team-review-requested:org/team`.
+    pub fn team_review_requested(self, org: &str, team: &str) -> Self {
+        self.qualifier(Key::TeamReviewRequested, &format!("{}/{}", org, team))
+    }
+
+    /// *Adds* a `team` statement to the query, for issues and pull requests
+    /// that mention the team.
+    ///
+    /// Results in `team:org/team`.
+    pub fn team(self, org: &str, team: &str) -> Self {
+        self.qualifier(Key::Team, &format!("{}/{}", org, team))
+    }
+
+    /// *Adds* any qualifier to the query, for qualifiers and values that
+    /// don't have their own methods yet.
+    ///
+    /// Results in `name:value`. Raw qualifiers are not checked before
+    /// searching.
+    ///
+    /// ```
+    /// use github_stats::Query;
+    ///
+    /// let query = Query::new().raw("linked", "pr");
+    ///
+    /// assert_eq!("linked:pr", query.to_string());
+    /// ```
+    pub fn raw(self, name: &str, value: &str) -> Self {
+        self.qualifier(Key::Raw(String::from(name)), value)
+    }
+
+    /// *Excludes* results that match any of the qualifiers in `query`.
+    ///
+    /// Results in `-qualifier:value` for each qualifier, or in `NOT keyword`
+    /// for keywords. [Github] can't exclude groups or [`in`] statements, so
+    /// searching with them fails with [`Error::InvalidQuery`].
+    ///
+    /// [Github]: https://github.com/
+    /// [`in`]: #method.in
+    /// [`Error::InvalidQuery`]: ../enum.Error.html#variant.InvalidQuery
+    pub fn not(mut self, query: Query) -> Self {
+        for term in query.terms {
+            self = self.push(term.negate());
+        }
+        self
+    }
+
+    /// *Adds* a group of queries, any of which can match.
+    ///
+    /// Results in `(first OR second)`.
+    pub fn any(self, queries: impl IntoIterator<Item = Query>) -> Self {
+        let mut queries: Vec<Query> =
+            queries.into_iter().filter(|q| !q.terms.is_empty()).collect();
+        match queries.len() {
+            0 => self,
+            1 => queries.remove(0).terms.into_iter().fold(self, Query::push),
+            _ => self.push(Term::Any(queries)),
+        }
+    }
+
+    // Fails if Github would reject the query, or if it uses values that
+    // can't be used when searching `area`. Github only documents `NOT` for
+    // keywords, so other negated terms are rejected too.
+    pub(crate) fn check(&self, area: &str) -> Result<()> {
+        let operators = self.operators();
+        if operators > MAX_OPERATORS {
+            let message = format!(
+                "{} AND, OR and NOT operators used, but at most {} are allowed",
+                operators, MAX_OPERATORS,
+            );
+            return Err(Error::InvalidQuery(message));
+        }
+        self.check_area(area)
+    }
+
+    fn operators(&self) -> usize {
+        self.terms.iter().map(Term::operators).sum()
+    }
+
+    fn check_area(&self, area: &str) -> Result<()> {
+        self.terms.iter().try_for_each(|t| t.check_area(area))
+    }
+
+    fn date_range<D: SearchDate>(self, key: Key, range: Range<D>) -> Self {
+        let range = range.map(|date| date.to_search_date());
+        self.qualifier(key, &range.to_string())
+    }
+
+    fn qualifier(self, key: Key, value: &str) -> Self {
+        self.push(Term::Qualifier(key, vec![String::from(value)]))
+    }
+
+    // Inserts after every term with the same or an earlier key.
+    fn push(mut self, term: Term) -> Self {
+        let order = term.order();
+        let index = self.terms.partition_point(|t| t.order() <= order);
+        self.terms.insert(index, term);
         self
     }
 }
 
+impl Login for str {
+    fn login(&self) -> &str {
+        self
+    }
+}
+
+impl Login for String {
+    fn login(&self) -> &str {
+        self
+    }
+}
+
+impl Login for User {
+    fn login(&self) -> &str {
+        self.login()
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    /// Parses [Github]'s search syntax.
+    ///
+    /// Fails with [`ParseQuery`] if the query is malformed.
+    ///
+    /// [Github]: https://github.com/
+    /// [`ParseQuery`]: ../enum.Error.html#variant.ParseQuery
+    fn from_str(s: &str) -> Result<Self> {
+        parse::parse(s)
+    }
+}
+
+impl Term {
+    // Sorts keywords first and groups last, and qualifiers by their keys.
+    fn order(&self) -> (u8, Option<&Key>) {
+        match self {
+            Term::Keyword(_) => (0, None),
+            Term::Qualifier(key, _) => (1, Some(key)),
+            Term::In(_) => (1, Some(&Key::In)),
+            Term::Any(_) => (2, None),
+            Term::Not(term) => term.order(),
+        }
+    }
+
+    fn negate(self) -> Term {
+        match self {
+            Term::Not(term) => *term,
+            term => Term::Not(Box::new(term)),
+        }
+    }
+
+    fn operators(&self) -> usize {
+        match self {
+            Term::Keyword(_) | Term::Qualifier(..) | Term::In(_) => 0,
+            Term::Any(queries) => {
+                let nested: usize = queries.iter().map(Query::operators).sum();
+                nested + queries.len().saturating_sub(1)
+            }
+            // Negated qualifiers use `-` instead of `NOT`.
+            Term::Not(term) => match **term {
+                Term::Qualifier(..) => 0,
+                _ => 1 + term.operators(),
+            },
+        }
+    }
+
+    fn check_area(&self, area: &str) -> Result<()> {
+        let (key, values) = match self {
+            Term::Qualifier(key @ Key::Is, values)
+            | Term::Qualifier(key @ Key::Type, values)
+            | Term::Qualifier(key @ Key::State, values)
+            | Term::Qualifier(key @ Key::In, values)
+            | Term::Qualifier(key @ Key::Fork, values) => (key, values.as_slice()),
+            Term::Keyword(_) | Term::Qualifier(..) => return Ok(()),
+            Term::In(In(_, field)) => (&Key::In, std::slice::from_ref(field)),
+            Term::Any(queries) => return queries.iter().try_for_each(|q| q.check_area(area)),
+            Term::Not(term) => match **term {
+                Term::Keyword(_) | Term::Qualifier(..) => return term.check_area(area),
+                _ => {
+                    let message = format!("`{}` can't be excluded with NOT", term);
+                    return Err(Error::InvalidQuery(message));
+                }
+            },
+        };
+        for value in values {
+            match qualifiers::areas(key.name(), value) {
+                Some(areas) if !areas.contains(&area) => {
+                    let message = format!(
+                        "`{}:{}` can't be used when searching {}",
+                        key.name(),
+                        value,
+                        area,
+                    );
+                    return Err(Error::InvalidQuery(message));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Key {
+    // Every key but `Raw`.
+    const NAMED: [Key; 39] = [
+        Key::Repo,
+        Key::Org,
+        Key::User,
+        Key::Is,
+        Key::In,
+        Key::Label,
+        Key::Type,
+        Key::State,
+        Key::No,
+        Key::Language,
+        Key::Topic,
+        Key::License,
+        Key::Archived,
+        Key::Mirror,
+        Key::Template,
+        Key::Fork,
+        Key::Created,
+        Key::Updated,
+        Key::Closed,
+        Key::Merged,
+        Key::Pushed,
+        Key::Comments,
+        Key::Reactions,
+        Key::Interactions,
+        Key::Stars,
+        Key::Forks,
+        Key::Size,
+        Key::Topics,
+        Key::GoodFirstIssues,
+        Key::HelpWantedIssues,
+        Key::Author,
+        Key::Assignee,
+        Key::Mentions,
+        Key::Commenter,
+        Key::Involves,
+        Key::ReviewedBy,
+        Key::ReviewRequested,
+        Key::TeamReviewRequested,
+        Key::Team,
+    ];
+
+    fn from_name(name: &str) -> Key {
+        Key::NAMED
+            .iter()
+            .find(|key| key.name() == name)
+            .cloned()
+            .unwrap_or_else(|| Key::Raw(String::from(name)))
+    }
+
+    fn is_date_range(&self) -> bool {
+        matches!(
+            self,
+            Key::Created | Key::Updated | Key::Closed | Key::Merged | Key::Pushed
+        )
+    }
+
+    fn is_number_range(&self) -> bool {
+        matches!(
+            self,
+            Key::Comments
+                | Key::Reactions
+                | Key::Interactions
+                | Key::Stars
+                | Key::Forks
+                | Key::Size
+                | Key::Topics
+                | Key::GoodFirstIssues
+                | Key::HelpWantedIssues
+        )
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Key::Repo => "repo",
+            Key::Org => "org",
+            Key::User => "user",
+            Key::Is => "is",
+            Key::In => "in",
+            Key::Label => "label",
+            Key::Type => "type",
+            Key::State => "state",
+            Key::No => "no",
+            Key::Language => "language",
+            Key::Topic => "topic",
+            Key::License => "license",
+            Key::Archived => "archived",
+            Key::Mirror => "mirror",
+            Key::Template => "template",
+            Key::Fork => "fork",
+            Key::Created => "created",
+            Key::Updated => "updated",
+            Key::Closed => "closed",
+            Key::Merged => "merged",
+            Key::Pushed => "pushed",
+            Key::Comments => "comments",
+            Key::Reactions => "reactions",
+            Key::Interactions => "interactions",
+            Key::Stars => "stars",
+            Key::Forks => "forks",
+            Key::Size => "size",
+            Key::Topics => "topics",
+            Key::GoodFirstIssues => "good-first-issues",
+            Key::HelpWantedIssues => "help-wanted-issues",
+            Key::Author => "author",
+            Key::Assignee => "assignee",
+            Key::Mentions => "mentions",
+            Key::Commenter => "commenter",
+            Key::Involves => "involves",
+            Key::ReviewedBy => "reviewed-by",
+            Key::ReviewRequested => "review-requested",
+            Key::TeamReviewRequested => "team-review-requested",
+            Key::Team => "team",
+            Key::Raw(name) => name,
+        }
+    }
+}
+
 impl fmt::Display for Query {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let queries = {
-            let mut repo: Vec<String> = self.repo.iter().map(|s| format!("repo:{}", s)).collect();
-            let mut is: Vec<String> = self.is.iter().map(|s| format!("is:{}", s)).collect();
-            let mut r#in: Vec<String> = self.r#in.iter().map(|s| s.to_string()).collect();
-            let mut label: Vec<String> = self.label.iter().map(|s| format!("label:{}", s)).collect();
-            let mut r#type: Vec<String> =
-                self.r#type.iter().map(|s| format!("type:{}", s)).collect();
-            let mut state: Vec<String> =
-                self.state.iter().map(|s| format!("state:{}", s)).collect();
-            let mut no: Vec<String> =
-                self.no.iter().map(|s| format!("no:{}", s)).collect();
-            let mut language: Vec<String> =
-                self.language.iter().map(|s| format!("language:{}", s)).collect();
-
-            let mut queries: Vec<String> =
-                Vec::with_capacity(repo.len() + is.len() + label.len() + r#type.len() + state.len()
-                    + no.len());
-
-            queries.append(&mut repo);
-            queries.append(&mut is);
-            queries.append(&mut r#in);
-            queries.append(&mut label);
-            queries.append(&mut r#type);
-            queries.append(&mut state);
-            queries.append(&mut no);
-            queries.append(&mut language);
-            queries
-        };
+        let terms: Vec<String> = self.terms.iter().map(|t| t.to_string()).collect();
 
-        let queries = queries.join("+");
+        write!(f, "{}", terms.join(" "))
+    }
+}
 
-        write!(f, "q={}", queries)
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Term::Keyword(keyword) => write!(f, "{}", quote_keyword(keyword)),
+            Term::Qualifier(key, values) => {
+                let values: Vec<Cow<str>> = values.iter().map(|v| quote(v)).collect();
+                write!(f, "{}:{}", key.name(), values.join(","))
+            }
+            Term::In(r#in) => write!(f, "{}", r#in),
+            // Only parsed queries can have a group with one query.
+            Term::Any(queries) if queries.len() == 1 => write!(f, "({})", queries[0]),
+            Term::Any(queries) => {
+                let queries: Vec<String> = queries.iter().map(group).collect();
+                write!(f, "({})", queries.join(" OR "))
+            }
+            Term::Not(term) => match **term {
+                Term::Qualifier(..) => write!(f, "-{}", term),
+                _ => write!(f, "NOT {}", term),
+            },
+        }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 struct In(String, String);
 
 impl fmt::Display for In {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} in:{}", self.0, self.1)
+        write!(f, "{} in:{}", quote_keyword(&self.0), quote(&self.1))
+    }
+}
+
+// Puts a query in parentheses if it has more than one term.
+fn group(query: &Query) -> String {
+    if query.terms.len() > 1 {
+        format!("({})", query)
+    } else {
+        query.to_string()
+    }
+}
+
+// Like `quote`, but also quotes keywords that would otherwise be read as
+// qualifiers or operators.
+fn quote_keyword(keyword: &str) -> Cow<'_, str> {
+    let needs_quotes = keyword.contains(':') || ["AND", "OR", "NOT"].contains(&keyword);
+    if needs_quotes {
+        Cow::Owned(quoted(keyword))
+    } else {
+        quote(keyword)
+    }
+}
+
+// Puts a value in quotes if it would otherwise be read as more than one term,
+// escaping any quotes and backslashes inside it.
+fn quote(value: &str) -> Cow<'_, str> {
+    let special = |c: char| c.is_whitespace() || ['"', ',', '(', ')'].contains(&c);
+    if value.is_empty() || value.contains(special) {
+        Cow::Owned(quoted(value))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+fn quoted(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
     }
+    quoted.push('"');
+    quoted
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
 
     #[test]
     fn built_query() {
         let query = Query::new()
             .repo("rust-lang", "rust")
-            .r#type("pr")
-            .is("merged")
+            .r#type(Type::Pr)
+            .is(Is::Merged)
             .label("hacktoberfest")
             .no("assignee")
-            .r#in("[BUG]", "name")
+            .r#in("[BUG]", InField::Name)
             .language("rust")
             .to_string();
 
         assert_eq!(
-            "q=repo:rust-lang/rust+is:merged+[BUG] in:name+label:hacktoberfest+type:pr+no:assignee+language:rust",
+            "repo:rust-lang/rust is:merged [BUG] in:name label:hacktoberfest type:pr no:assignee language:rust",
             query
         );
     }
@@ -164,4 +920,178 @@ mod tests {
 
         assert_eq!("Users in:title", r#in.to_string());
     }
+
+    #[test]
+    fn state() {
+        let query = Query::new().state(State::Closed).is(Is::Issue).to_string();
+
+        assert_eq!("is:issue state:closed", query);
+    }
+
+    #[test]
+    fn ranges() {
+        let week = NaiveDate::from_ymd_opt(2020, 1, 24).unwrap();
+        let time = Utc.with_ymd_and_hms(2020, 1, 31, 12, 0, 0).unwrap();
+        let query = Query::new()
+            .is(Is::Pr)
+            .merged(week..)
+            .created(..=time)
+            .comments(Range::Greater(10))
+            .size(..1000)
+            .stars(10..=100)
+            .to_string();
+
+        assert_eq!(
+            "is:pr created:<=2020-01-31T12:00:00Z merged:>=2020-01-24 comments:>10 stars:10..100 size:<1000",
+            query
+        );
+    }
+
+    #[test]
+    fn people() {
+        let user: User =
+            serde_json::from_str(include_str!("../../tests/fixtures/users/octocat.json")).unwrap();
+        let query = Query::new()
+            .involves(&user)
+            .author("rust-highfive")
+            .reviewed_by(&String::from("bors"))
+            .team_review_requested("rust-lang", "compiler")
+            .to_string();
+
+        assert_eq!(
+            "author:rust-highfive involves:octocat reviewed-by:bors team-review-requested:rust-lang/compiler",
+            query
+        );
+    }
+
+    #[test]
+    fn repositories() {
+        let query = Query::new()
+            .help_wanted_issues(1..)
+            .fork(Fork::Include)
+            .archived(false)
+            .topic("cli")
+            .org("rust-lang")
+            .is(Is::Public)
+            .license("mit")
+            .topics(..=5)
+            .to_string();
+
+        assert_eq!(
+            "org:rust-lang is:public topic:cli license:mit archived:false fork:true topics:<=5 help-wanted-issues:>=1",
+            query
+        );
+
+        let query = Query::new().user("octocat").fork(Fork::Only);
+        assert!(query.check("repositories").is_ok());
+        assert!(query.check("issues").is_err());
+    }
+
+    #[test]
+    fn negated() {
+        let query = Query::new()
+            .is(Is::Pr)
+            .not(Query::new().label("wontfix").keyword("WIP"))
+            .not(Query::new().not(Query::new().author("bors")));
+
+        assert_eq!(
+            "NOT WIP is:pr -label:wontfix author:bors",
+            query.to_string()
+        );
+        assert!(query.check("issues").is_ok());
+    }
+
+    #[test]
+    fn negated_groups() {
+        let query = Query::new().not(Query::new().any(vec![
+            Query::new().is(Is::Draft),
+            Query::new().no("label"),
+        ]));
+        match query.check("issues") {
+            Err(Error::InvalidQuery(message)) => assert_eq!(
+                "`(is:draft OR no:label)` can't be excluded with NOT",
+                message,
+            ),
+            other => panic!("expected InvalidQuery, got {:?}", other),
+        }
+
+        let query = Query::new().not(Query::new().r#in("WIP", InField::Title));
+        assert!(query.check("issues").is_err());
+    }
+
+    #[test]
+    fn any() {
+        let query = Query::new()
+            .any(vec![
+                Query::new().is(Is::Pr).author("octocat"),
+                Query::new().label("good first issue"),
+            ])
+            .any(vec![Query::new()])
+            .any(vec![Query::new().state(State::Open)])
+            .any_label(&["bug", "needs review"])
+            .to_string();
+
+        assert_eq!(
+            r#"label:bug,"needs review" state:open ((is:pr author:octocat) OR label:"good first issue")"#,
+            query
+        );
+    }
+
+    #[test]
+    fn operators() {
+        let labels = Query::new().any(vec![
+            Query::new().label("bug"),
+            Query::new().label("regression"),
+            Query::new().label("crash"),
+        ]);
+        let query = Query::new()
+            .any(vec![Query::new().is(Is::Pr), Query::new().is(Is::Issue)])
+            .any(vec![labels, Query::new().no("label")])
+            .not(Query::new().keyword("WIP").label("wontfix"));
+
+        assert_eq!(5, query.operators());
+        assert!(query.check("issues").is_ok());
+
+        match query.not(Query::new().keyword("RFC")).check("issues") {
+            Err(Error::InvalidQuery(message)) => assert!(message.starts_with("6 AND, OR")),
+            other => panic!("expected InvalidQuery, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn areas() {
+        let query = Query::new()
+            .is(Is::Public)
+            .r#in("rust", InField::Readme)
+            .raw("is", "merged");
+        assert!(query.check("repositories").is_ok());
+
+        let query = query.not(Query::new().is(Is::Merged));
+        match query.check("repositories") {
+            Err(Error::InvalidQuery(message)) => assert_eq!(
+                "`is:merged` can't be used when searching repositories",
+                message,
+            ),
+            other => panic!("expected InvalidQuery, got {:?}", other),
+        }
+
+        let query = Query::new().r#type(Type::Org).r#in("rust", InField::Title);
+        assert!(query.check("users").is_err());
+        assert!(query.check("issues").is_err());
+    }
+
+    #[test]
+    fn quoted_values() {
+        let query = Query::new()
+            .label("good first issue")
+            .r#in("cannot borrow", InField::Title)
+            .label(r#"say "hi""#)
+            .label("")
+            .to_string();
+
+        assert_eq!(
+            r#""cannot borrow" in:title label:"good first issue" label:"say \"hi\"" label:"""#,
+            query
+        );
+    }
 }
@@ -0,0 +1,477 @@
+//! Parses [Github]'s search syntax into a `Query`.
+//!
+//! [Github]: https://github.com/
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+use super::{In, Key, Query, Term};
+use crate::search::qualifiers;
+use crate::{Error, Result};
+
+pub(super) fn parse(input: &str) -> Result<Query> {
+    let mut parser = Parser { input, position: 0 };
+    let members = parser.members(None)?;
+    Ok(Query::new().any(members))
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    // Byte offset of the next character.
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    // Parses queries separated by `OR`, until the end of the input, or until
+    // the end of the group whose opening parenthesis is at `group`.
+    fn members(&mut self, group: Option<usize>) -> Result<Vec<Query>> {
+        let mut members = Vec::new();
+        loop {
+            let member = self.terms(group)?;
+            let empty = member.terms.is_empty();
+            if self.consume_operator("OR") {
+                if empty {
+                    return Err(self.error_at(self.position - 2, "expected a term before OR"));
+                }
+                members.push(member);
+                continue;
+            }
+            if empty && !members.is_empty() {
+                return Err(self.error("expected a term after OR"));
+            }
+            members.push(member);
+            return Ok(members);
+        }
+    }
+
+    // Parses terms until the end of the input, the end of the group whose
+    // opening parenthesis is at `group`, or an `OR`.
+    fn terms(&mut self, group: Option<usize>) -> Result<Query> {
+        let mut query = Query::new();
+        loop {
+            self.skip_whitespace();
+            match (self.peek(), group) {
+                (None, Some(open)) => return Err(self.error_at(open, "unclosed parenthesis")),
+                (None, None) | (Some(')'), Some(_)) => return Ok(query),
+                (Some(')'), None) => return Err(self.error("unexpected closing parenthesis")),
+                _ if self.peek_operator("OR") => return Ok(query),
+                _ if self.consume_operator("AND") => {}
+                _ => query = self.term()?.into_iter().fold(query, Query::push),
+            }
+        }
+    }
+
+    // Parses one term. A group of one query is flattened into the query's
+    // terms, and a keyword can be followed by a qualifier that is not `in:`.
+    fn term(&mut self) -> Result<Vec<Term>> {
+        if self.consume_operator("NOT") {
+            self.skip_whitespace();
+            let ends = self.peek().is_none() || self.peek() == Some(')');
+            if ends || self.peek_operator("OR") || self.peek_operator("AND") {
+                return Err(self.error("expected a term after NOT"));
+            }
+            if self.peek() == Some('(') {
+                let mut members = self.group()?;
+                let term = if members.len() == 1 && members[0].terms.len() == 1 {
+                    members.remove(0).terms.remove(0)
+                } else {
+                    Term::Any(members)
+                };
+                return Ok(vec![term.negate()]);
+            }
+            let mut terms = self.term()?;
+            let first = terms.remove(0);
+            terms.insert(0, first.negate());
+            return Ok(terms);
+        }
+
+        match self.peek() {
+            Some('(') => {
+                let mut members = self.group()?;
+                if members.len() == 1 {
+                    Ok(members.remove(0).terms)
+                } else {
+                    Ok(vec![Term::Any(members)])
+                }
+            }
+            Some('-') if self.qualifier_name(self.position + 1).is_some() => {
+                self.position += 1;
+                Ok(vec![self.qualifier()?.negate()])
+            }
+            Some(_) if self.qualifier_name(self.position).is_some() => Ok(vec![self.qualifier()?]),
+            Some(_) => {
+                let keyword = if self.peek() == Some('"') {
+                    self.quoted()?
+                } else {
+                    self.bare(&['(', ')'])
+                };
+                self.keyword(keyword)
+            }
+            None => Err(self.error("expected a term")),
+        }
+    }
+
+    // Parses a group, starting at its opening parenthesis.
+    fn group(&mut self) -> Result<Vec<Query>> {
+        let open = self.position;
+        self.position += 1;
+        let members = self.members(Some(open))?;
+        // `members` stops at the closing parenthesis.
+        self.position += 1;
+        if members.len() == 1 && members[0].terms.is_empty() {
+            return Err(self.error_at(open, "empty parentheses"));
+        }
+        Ok(members)
+    }
+
+    // Attaches `keyword` to an `in:` qualifier right after it.
+    fn keyword(&mut self, keyword: String) -> Result<Vec<Term>> {
+        let start = self.position;
+        self.skip_whitespace();
+        if self.qualifier_name(self.position) != Some("in") {
+            self.position = start;
+            return Ok(vec![Term::Keyword(keyword)]);
+        }
+        match self.qualifier()? {
+            Term::Qualifier(Key::In, mut fields) if fields.len() == 1 => {
+                Ok(vec![Term::In(In(keyword, fields.remove(0)))])
+            }
+            qualifier => Ok(vec![Term::Keyword(keyword), qualifier]),
+        }
+    }
+
+    // Parses `name:value`, or `name:first,second`.
+    fn qualifier(&mut self) -> Result<Term> {
+        let name = self.qualifier_name(self.position).unwrap();
+        self.position += name.len() + 1;
+
+        let mut values = Vec::new();
+        loop {
+            let start = self.position;
+            let value = match self.peek() {
+                Some('"') => self.quoted()?,
+                _ => self.bare(&[',', '(', ')']),
+            };
+            if value.is_empty() && self.position == start {
+                return Err(self.error(&format!("expected a value for `{}`", name)));
+            }
+            values.push((start, value));
+            if self.peek() != Some(',') {
+                break;
+            }
+            self.position += 1;
+        }
+
+        let key = Key::from_name(name);
+        let valid: Option<fn(&str) -> bool> = if key.is_date_range() {
+            Some(is_date)
+        } else if key.is_number_range() {
+            Some(is_number)
+        } else {
+            None
+        };
+        if let Some(valid) = valid {
+            if let Some((start, value)) = values.iter().find(|(_, v)| !is_range(v, valid)) {
+                let message = format!("invalid range `{}` for `{}`", value, name);
+                return Err(self.error_at(*start, &message));
+            }
+        }
+        // Values that aren't typed are kept as they are.
+        let key = match key {
+            Key::Is | Key::Type | Key::State | Key::In | Key::Fork
+                if values
+                    .iter()
+                    .any(|(_, v)| qualifiers::areas(name, v).is_none()) =>
+            {
+                Key::Raw(String::from(name))
+            }
+            key => key,
+        };
+
+        let values = values.into_iter().map(|(_, value)| value).collect();
+        Ok(Term::Qualifier(key, values))
+    }
+
+    // The name of the qualifier starting at `position`, if there is one.
+    fn qualifier_name(&self, position: usize) -> Option<&'a str> {
+        let rest = &self.input[position..];
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(rest.len());
+        if end > 0 && rest[end..].starts_with(':') {
+            Some(&rest[..end])
+        } else {
+            None
+        }
+    }
+
+    // Parses a quoted string, starting at its opening quote.
+    fn quoted(&mut self) -> Result<String> {
+        let open = self.position;
+        let mut value = String::new();
+        let mut chars = self.input[open + 1..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.position = open + 1 + i + 1;
+                    return Ok(value);
+                }
+                '\\' => {
+                    if let Some((_, escaped)) = chars.next() {
+                        value.push(escaped);
+                    }
+                }
+                c => value.push(c),
+            }
+        }
+        Err(self.error_at(open, "unclosed quote"))
+    }
+
+    // Parses until whitespace or one of `stops`.
+    fn bare(&mut self, stops: &[char]) -> String {
+        let rest = &self.input[self.position..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || stops.contains(&c))
+            .unwrap_or(rest.len());
+        self.position += end;
+        String::from(&rest[..end])
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    // `true` if `operator` is next, as a whole word.
+    fn peek_operator(&self, operator: &str) -> bool {
+        let rest = &self.input[self.position..];
+        if !rest.starts_with(operator) {
+            return false;
+        }
+        match rest[operator.len()..].chars().next() {
+            Some(c) => c.is_whitespace() || c == '(' || c == ')',
+            None => true,
+        }
+    }
+
+    fn consume_operator(&mut self, operator: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.peek_operator(operator);
+        if found {
+            self.position += operator.len();
+        }
+        found
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, message: &str) -> Error {
+        self.error_at(self.position, message)
+    }
+
+    fn error_at(&self, position: usize, message: &str) -> Error {
+        Error::ParseQuery {
+            position,
+            message: String::from(message),
+        }
+    }
+}
+
+// `>value`, `>=value`, `<value`, `<=value`, `start..end` or `value`, where
+// either end of `start..end` can be `*`.
+fn is_range(range: &str, valid: fn(&str) -> bool) -> bool {
+    let comparison = [">=", "<=", ">", "<"]
+        .iter()
+        .find_map(|op| range.strip_prefix(op));
+    if let Some(value) = comparison {
+        return valid(value);
+    }
+    match range.split_once("..") {
+        Some(("*", "*")) => false,
+        Some((start, end)) => (start == "*" || valid(start)) && (end == "*" || valid(end)),
+        None => valid(range),
+    }
+}
+
+fn is_date(value: &str) -> bool {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+        || NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").is_ok()
+        || DateTime::parse_from_rfc3339(value).is_ok()
+}
+
+fn is_number(value: &str) -> bool {
+    value.parse::<u64>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{Fork, InField, Is, Range, State, Type};
+    use chrono::{TimeZone, Utc};
+
+    fn parse_error(input: &str) -> (usize, String) {
+        match parse(input) {
+            Err(Error::ParseQuery { position, message }) => (position, message),
+            other => panic!("expected ParseQuery, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let date = NaiveDate::from_ymd_opt(2020, 1, 24).unwrap();
+        let time = Utc.with_ymd_and_hms(2020, 1, 31, 12, 0, 0).unwrap();
+        let queries = vec![
+            Query::new(),
+            Query::new()
+                .repo("rust-lang", "rust")
+                .r#type(Type::Pr)
+                .is(Is::Merged)
+                .label("hacktoberfest")
+                .no("assignee")
+                .r#in("[BUG]", InField::Name)
+                .language("rust"),
+            Query::new()
+                .keyword("borrow checker")
+                .keyword("E0502")
+                .state(State::Open)
+                .label("good first issue")
+                .any_label(&["A-diagnostics", "needs, comma"]),
+            Query::new()
+                .merged(date..)
+                .created(..=time)
+                .comments(Range::Greater(10))
+                .stars(10..=100)
+                .size(..1000),
+            Query::new()
+                .author("octocat")
+                .reviewed_by("bors")
+                .team_review_requested("rust-lang", "compiler"),
+            Query::new()
+                .org("rust-lang")
+                .topic("compiler")
+                .license("apache-2.0")
+                .mirror(false)
+                .template(true)
+                .fork(Fork::Only)
+                .good_first_issues(1..),
+            Query::new()
+                .is(Is::Pr)
+                .not(Query::new().label("wontfix").r#in("WIP", InField::Title))
+                .not(Query::new().keyword("AND"))
+                .any(vec![
+                    Query::new().is(Is::Draft).author("octocat"),
+                    Query::new().label(r#"say "hi" (\o/)"#),
+                ])
+                .not(Query::new().any(vec![
+                    Query::new().no("label"),
+                    Query::new().any(vec![Query::new().keyword("a"), Query::new().keyword("b")]),
+                ])),
+            Query::new()
+                .raw("linked", "pr")
+                .raw("is", "blocked")
+                .keyword("key:value"),
+        ];
+
+        for query in queries {
+            let parsed: Query = query.to_string().parse().unwrap();
+            assert_eq!(query, parsed, "{}", query);
+        }
+    }
+
+    #[test]
+    fn github_syntax() {
+        let query = parse(
+            r#"is:issue  is:open label:"good first issue" -label:wontfix author:octocat
+            created:2020-01-01..2020-12-31 sort:updated-desc cannot in:title"#,
+        )
+        .unwrap();
+        let expected = Query::new()
+            .is(Is::Issue)
+            .is(Is::Open)
+            .label("good first issue")
+            .not(Query::new().label("wontfix"))
+            .author("octocat")
+            .created(Range::Between(
+                NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2020, 12, 31).unwrap(),
+            ))
+            .raw("sort", "updated-desc")
+            .r#in("cannot", InField::Title);
+
+        assert_eq!(expected, query);
+    }
+
+    #[test]
+    fn operators() {
+        let query = parse("is:pr AND (author:a OR author:b) OR label:bug").unwrap();
+        let expected = Query::new().any(vec![
+            Query::new()
+                .is(Is::Pr)
+                .any(vec![Query::new().author("a"), Query::new().author("b")]),
+            Query::new().label("bug"),
+        ]);
+        assert_eq!(expected, query);
+
+        let query = parse("NOT (is:pr) NOT NOT rust NOT (is:draft is:pr)").unwrap();
+        assert_eq!("rust -is:pr NOT (is:draft is:pr)", query.to_string());
+        assert_eq!(query, query.to_string().parse().unwrap());
+    }
+
+    #[test]
+    fn typed_values() {
+        let query = parse("is:merge is:pr,issue in:title").unwrap();
+
+        assert_eq!("is:pr,issue in:title is:merge", query.to_string());
+        assert!(query.check("repositories").is_err());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            (6, String::from("unclosed quote")),
+            parse_error(r#"label:"bug"#)
+        );
+        assert_eq!(
+            (6, String::from("expected a value for `label`")),
+            parse_error("label: is:pr"),
+        );
+        assert_eq!(
+            (
+                18,
+                String::from("invalid range `>=2020-13-01` for `created`")
+            ),
+            parse_error("label:a,b created:>=2020-13-01"),
+        );
+        assert_eq!(
+            (6, String::from("invalid range `ten` for `stars`")),
+            parse_error("stars:ten"),
+        );
+        assert_eq!(
+            (0, String::from("unclosed parenthesis")),
+            parse_error("(is:pr")
+        );
+        assert_eq!(
+            (5, String::from("unexpected closing parenthesis")),
+            parse_error("is:pr)"),
+        );
+        assert_eq!((0, String::from("empty parentheses")), parse_error("()"));
+        assert_eq!(
+            (0, String::from("expected a term before OR")),
+            parse_error("OR is:pr"),
+        );
+        assert_eq!(
+            (10, String::from("expected a term after OR")),
+            parse_error("(is:pr OR )"),
+        );
+        assert_eq!(
+            (9, String::from("expected a term after NOT")),
+            parse_error("is:pr NOT"),
+        );
+        // Positions are byte offsets.
+        assert_eq!(
+            (21, String::from("invalid range `soon` for `created`")),
+            parse_error("label:日本 created:soon"),
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use std::ops::{RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, TimeZone};
+
+/// A range of dates or numbers, for qualifiers like `created` or `stars`.
+///
+/// Can be created from Rust's range syntax, except for [`Greater`], which has
+/// no range syntax:
+///
+/// | Range      | Search syntax |
+/// |------------|---------------|
+/// | `a..`      | `>=a`         |
+/// | `..b`      | `<b`          |
+/// | `..=b`     | `<=b`         |
+/// | `a..=b`    | `a..b`        |
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use github_stats::search::Range;
+/// use github_stats::Query;
+///
+/// let last_week = NaiveDate::from_ymd_opt(2020, 1, 24).unwrap();
+/// let query = Query::new()
+///     .merged(last_week..)
+///     .stars(Range::Greater(100))
+///     .comments(1..=5);
+///
+/// assert_eq!("merged:>=2020-01-24 comments:1..5 stars:>100", query.to_string());
+/// ```
+///
+/// [`Greater`]: #variant.Greater
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Range<T> {
+    /// `>value`
+    Greater(T),
+    /// `>=value`
+    GreaterOrEqual(T),
+    /// `<value`
+    Less(T),
+    /// `<=value`
+    LessOrEqual(T),
+    /// `start..end`, including both `start` and `end`.
+    Between(T, T),
+}
+
+/// A date, or a date and time, that can be used in a [`Range`].
+///
+/// [`Range`]: enum.Range.html
+pub trait SearchDate {
+    /// Formats the date like `2020-01-31`, or like `2020-01-31T12:00:00Z` if
+    /// it has a time.
+    fn to_search_date(&self) -> String;
+}
+
+impl<T> Range<T> {
+    pub(crate) fn map<U>(self, f: impl Fn(T) -> U) -> Range<U> {
+        match self {
+            Range::Greater(value) => Range::Greater(f(value)),
+            Range::GreaterOrEqual(value) => Range::GreaterOrEqual(f(value)),
+            Range::Less(value) => Range::Less(f(value)),
+            Range::LessOrEqual(value) => Range::LessOrEqual(f(value)),
+            Range::Between(start, end) => Range::Between(f(start), f(end)),
+        }
+    }
+}
+
+impl<T> From<RangeFrom<T>> for Range<T> {
+    fn from(range: RangeFrom<T>) -> Self {
+        Range::GreaterOrEqual(range.start)
+    }
+}
+
+impl<T> From<RangeTo<T>> for Range<T> {
+    fn from(range: RangeTo<T>) -> Self {
+        Range::Less(range.end)
+    }
+}
+
+impl<T> From<RangeToInclusive<T>> for Range<T> {
+    fn from(range: RangeToInclusive<T>) -> Self {
+        Range::LessOrEqual(range.end)
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for Range<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Range::Between(start, end)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Range<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Range::Greater(value) => write!(f, ">{}", value),
+            Range::GreaterOrEqual(value) => write!(f, ">={}", value),
+            Range::Less(value) => write!(f, "<{}", value),
+            Range::LessOrEqual(value) => write!(f, "<={}", value),
+            Range::Between(start, end) => write!(f, "{}..{}", start, end),
+        }
+    }
+}
+
+impl SearchDate for NaiveDate {
+    fn to_search_date(&self) -> String {
+        self.format("%Y-%m-%d").to_string()
+    }
+}
+
+impl<Tz: TimeZone> SearchDate for DateTime<Tz>
+where
+    Tz::Offset: fmt::Display,
+{
+    fn to_search_date(&self) -> String {
+        self.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    #[test]
+    fn from_ranges() {
+        assert_eq!(Range::GreaterOrEqual(1), Range::from(1..));
+        assert_eq!(Range::Less(2), Range::from(..2));
+        assert_eq!(Range::LessOrEqual(3), Range::from(..=3));
+        assert_eq!(Range::Between(4, 5), Range::from(4..=5));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(">10", Range::Greater(10).to_string());
+        assert_eq!(">=10", Range::GreaterOrEqual(10).to_string());
+        assert_eq!("<10", Range::Less(10).to_string());
+        assert_eq!("<=10", Range::LessOrEqual(10).to_string());
+        assert_eq!("10..20", Range::Between(10, 20).to_string());
+    }
+
+    #[test]
+    fn dates() {
+        let date = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+        let utc = Utc.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).unwrap();
+        let offset = utc.with_timezone(&FixedOffset::east_opt(2 * 3600).unwrap());
+
+        assert_eq!("2020-01-02", date.to_search_date());
+        assert_eq!("2020-01-02T03:04:05Z", utc.to_search_date());
+        assert_eq!("2020-01-02T05:04:05+02:00", offset.to_search_date());
+    }
+}
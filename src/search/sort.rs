@@ -0,0 +1,160 @@
+use std::fmt;
+
+/// The order of sorted search results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    /// The default.
+    Desc,
+}
+
+/// A way of sorting search results.
+///
+/// Each kind of [`SearchItem`] has its own sort keys.
+///
+/// [`SearchItem`]: trait.SearchItem.html
+pub trait SortKey: Copy {
+    /// The key as it is used in the URL, like `"updated"`.
+    fn as_str(self) -> &'static str;
+}
+
+/// Sorts issues and pull requests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IssueSort {
+    Comments,
+    Reactions,
+    /// Reactions and comments.
+    Interactions,
+    Created,
+    Updated,
+}
+
+/// Sorts repositories.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RepoSort {
+    Stars,
+    Forks,
+    HelpWantedIssues,
+    Updated,
+}
+
+/// Sorts users.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UserSort {
+    Followers,
+    Repositories,
+    Joined,
+}
+
+/// Sorts code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CodeSort {
+    /// When files were last indexed by [Github].
+    ///
+    /// [Github]: https://github.com/
+    Indexed,
+}
+
+/// Sorts commits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommitSort {
+    AuthorDate,
+    CommitterDate,
+}
+
+/// Sorts labels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LabelSort {
+    Created,
+    Updated,
+}
+
+/// Topics can't be sorted, so this has no values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TopicSort {}
+
+impl Order {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Order::Asc => "asc",
+            Order::Desc => "desc",
+        }
+    }
+}
+
+impl SortKey for IssueSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            IssueSort::Comments => "comments",
+            IssueSort::Reactions => "reactions",
+            IssueSort::Interactions => "interactions",
+            IssueSort::Created => "created",
+            IssueSort::Updated => "updated",
+        }
+    }
+}
+
+impl SortKey for RepoSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            RepoSort::Stars => "stars",
+            RepoSort::Forks => "forks",
+            RepoSort::HelpWantedIssues => "help-wanted-issues",
+            RepoSort::Updated => "updated",
+        }
+    }
+}
+
+impl SortKey for UserSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            UserSort::Followers => "followers",
+            UserSort::Repositories => "repositories",
+            UserSort::Joined => "joined",
+        }
+    }
+}
+
+impl SortKey for CodeSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            CodeSort::Indexed => "indexed",
+        }
+    }
+}
+
+impl SortKey for CommitSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            CommitSort::AuthorDate => "author-date",
+            CommitSort::CommitterDate => "committer-date",
+        }
+    }
+}
+
+impl SortKey for LabelSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            LabelSort::Created => "created",
+            LabelSort::Updated => "updated",
+        }
+    }
+}
+
+impl SortKey for TopicSort {
+    fn as_str(self) -> &'static str {
+        match self {}
+    }
+}
+
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -1,13 +1,25 @@
 //! For getting user information.
 
+use chrono::prelude::{DateTime, Utc};
 use serde::Deserialize;
 
-use crate::Result;
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+#[cfg(feature = "blocking")]
+use crate::Client;
+use crate::{RateLimit, Result};
 
 /// Represents that stats of a [Github] user.
 ///
+/// A `User` created with [`User::new`] has a full profile. A `User` that is
+/// part of another response, like [`Repo::owner`], only has the compact form,
+/// so its profile fields, like [`followers`], are `None`.
+///
 /// [Github]: https://github.com/
-#[derive(Debug, Deserialize)]
+/// [`User::new`]: #method.new
+/// [`Repo::owner`]: struct.Repo.html#method.owner
+/// [`followers`]: #method.followers
+#[derive(Debug, Clone, Deserialize)]
 pub struct User {
     login: String,
     id: u64,
@@ -16,22 +28,62 @@ pub struct User {
     gravatar_id: String,
     html_url: String,
     r#type: String,
+    name: Option<String>,
+    company: Option<String>,
+    blog: Option<String>,
+    location: Option<String>,
+    email: Option<String>,
+    bio: Option<String>,
+    twitter_username: Option<String>,
+    public_repos: Option<u64>,
+    public_gists: Option<u64>,
+    followers: Option<u64>,
+    following: Option<u64>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    rate_limit: Option<RateLimit>,
 }
 
 impl User {
-    /// Creates a new `User`
+    /// Creates a new `User` with a full profile.
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use github_stats::User;
     ///
     /// let user = User::new("rust-lang");
     /// ```
+    #[cfg(feature = "blocking")]
     pub fn new(user: &str) -> Result<Self> {
-        const URL: &str = "https://api.github.com/users";
-        let url = format!("{}/{}", URL, user);
-        let user: User = reqwest::get(&url)?.json()?;
+        User::new_with(&Client::new()?, user)
+    }
+
+    /// Creates a new `User` using a configured [`Client`].
+    ///
+    /// [`Client`]: struct.Client.html
+    #[cfg(feature = "blocking")]
+    pub fn new_with(client: &Client, user: &str) -> Result<Self> {
+        let response = client.get(&format!("/users/{}", user))?;
+        let user = User {
+            rate_limit: response.rate_limit,
+            ..response.value
+        };
+
+        Ok(user)
+    }
+
+    /// Creates a new `User` using an [`AsyncClient`].
+    ///
+    /// [`AsyncClient`]: struct.AsyncClient.html
+    #[cfg(feature = "async")]
+    pub async fn new_async(client: &AsyncClient, user: &str) -> Result<Self> {
+        let response = client.get(&format!("/users/{}", user)).await?;
+        let user = User {
+            rate_limit: response.rate_limit,
+            ..response.value
+        };
 
         Ok(user)
     }
@@ -58,4 +110,95 @@ impl User {
     pub fn r#type(&self) -> &str {
         &self.r#type
     }
+    /// `true` if this `User` has a full profile, and not just the compact
+    /// form that is part of other responses.
+    pub fn is_full_profile(&self) -> bool {
+        self.created_at.is_some()
+    }
+    /// Display name.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    pub fn company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+    /// Website, which may be `Some("")` if it was removed.
+    pub fn blog(&self) -> Option<&str> {
+        self.blog.as_deref()
+    }
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+    /// Public email.
+    pub fn email(&self) -> Option<&str> {
+        self.email.as_deref()
+    }
+    pub fn bio(&self) -> Option<&str> {
+        self.bio.as_deref()
+    }
+    pub fn twitter_username(&self) -> Option<&str> {
+        self.twitter_username.as_deref()
+    }
+    /// Number of public repositories.
+    pub fn public_repos(&self) -> Option<u64> {
+        self.public_repos
+    }
+    /// Number of public gists.
+    pub fn public_gists(&self) -> Option<u64> {
+        self.public_gists
+    }
+    pub fn followers(&self) -> Option<u64> {
+        self.followers
+    }
+    /// Number of users this user follows.
+    pub fn following(&self) -> Option<u64> {
+        self.following
+    }
+    pub fn created_at(&self) -> Option<&DateTime<Utc>> {
+        self.created_at.as_ref()
+    }
+    pub fn updated_at(&self) -> Option<&DateTime<Utc>> {
+        self.updated_at.as_ref()
+    }
+    /// The rate limit after fetching this `User`.
+    ///
+    /// `None` if the response did not report it, or if this `User` was part
+    /// of another response.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.rate_limit.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_profile() {
+        let user: User =
+            serde_json::from_str(include_str!("../tests/fixtures/users/octocat.json")).unwrap();
+
+        assert!(user.is_full_profile());
+        assert_eq!(Some("The Octocat"), user.name());
+        assert_eq!(Some("@github"), user.company());
+        assert_eq!(None, user.email());
+        assert_eq!(None, user.bio());
+        assert_eq!(Some(8), user.public_repos());
+        assert_eq!(Some(3938), user.followers());
+        assert_eq!(Some(9), user.following());
+        assert_eq!(1295981076, user.created_at().unwrap().timestamp());
+    }
+
+    #[test]
+    fn compact_owner() {
+        let repo: crate::Repo =
+            serde_json::from_str(include_str!("../tests/fixtures/repos/rust-lang-rust.json"))
+                .unwrap();
+        let owner = repo.owner();
+
+        assert!(!owner.is_full_profile());
+        assert_eq!("rust-lang", owner.login());
+        assert_eq!(None, owner.followers());
+        assert_eq!(None, owner.name());
+    }
 }